@@ -1,24 +1,61 @@
-use burn::{
-    backend::{wgpu::AutoGraphicsApi, Autodiff, Wgpu},
-    optim::AdamConfig,
-};
+use burn::optim::AdamConfig;
+use burn::tensor::backend::{AutodiffBackend, Backend};
+use inside_deep_learning_with_burn::cli::{self, Example};
+use inside_deep_learning_with_burn::evaluation::Evaluation;
+use inside_deep_learning_with_burn::inference::LoadError;
+use inside_deep_learning_with_burn::mlp_experiment::{self, MlpDataConfig, MlpExperimentConfig};
+use inside_deep_learning_with_burn::model::mlp::MlpConfig;
+use inside_deep_learning_with_burn::optimizer::OptimizerConfig;
+use inside_deep_learning_with_burn::split::SplitConfig;
+use inside_deep_learning_with_burn::toy_data::data::ToyDatasetConfig;
+use inside_deep_learning_with_burn::train::TrainingConfig;
 
-mod inference;
-mod model;
-mod training;
+struct ToyNetwork;
+
+impl Example for ToyNetwork {
+    const ARTIFACT_DIR: &'static str =
+        "examples/2-2-building-our-first-neural-network/toy_artifacts";
+
+    type Config = MlpExperimentConfig;
+
+    fn config() -> MlpExperimentConfig {
+        MlpExperimentConfig::new(
+            MlpConfig::new(1, vec![10], 1),
+            TrainingConfig::new(OptimizerConfig::Adam(AdamConfig::new()))
+                .with_num_epochs(256)
+                .with_batch_size(32)
+                .with_num_workers(32)
+                .with_learning_rate(1.0e-2),
+            MlpDataConfig::Toy(ToyDatasetConfig::new(
+                0.0,
+                20.0,
+                1000,
+                SplitConfig::new(0.8, 0.1, 12, false),
+            )),
+        )
+    }
+
+    fn train<B: AutodiffBackend>(
+        artifact_dir: &str,
+        config: MlpExperimentConfig,
+        device: B::Device,
+        checkpoint: Option<usize>,
+    ) {
+        mlp_experiment::train::<B>(artifact_dir, &config, device, checkpoint);
+    }
+
+    fn infer<B: Backend>(artifact_dir: &str, device: B::Device, _index: Option<usize>) {
+        mlp_experiment::infer::<B>(artifact_dir, device);
+    }
+
+    fn evaluate<B: Backend>(
+        artifact_dir: &str,
+        device: B::Device,
+    ) -> Result<Evaluation, LoadError> {
+        mlp_experiment::evaluate::<B>(artifact_dir, device)
+    }
+}
 
 fn main() {
-    type ToyBackend = Wgpu<AutoGraphicsApi, f32, i32>;
-    type ToyAutodiffBackend = Autodiff<ToyBackend>;
-
-    let device = burn::backend::wgpu::WgpuDevice::default();
-    let artifact_dir = "examples/2-2-building-our-first-neural-network/toy_artifacts";
-    training::train::<ToyAutodiffBackend>(
-        artifact_dir,
-        training::TrainingConfig::new(model::ModelConfig::new(1, 10, 1), AdamConfig::new())
-            .with_num_epochs(256),
-        device.clone(),
-    );
-
-    inference::infer::<ToyBackend>(artifact_dir, device);
+    cli::run::<ToyNetwork>();
 }
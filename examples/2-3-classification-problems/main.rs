@@ -1,24 +1,57 @@
-mod inference;
-mod model;
-mod training;
+use burn::optim::AdamConfig;
+use burn::tensor::backend::{AutodiffBackend, Backend};
+use inside_deep_learning_with_burn::cli::{self, Example};
+use inside_deep_learning_with_burn::evaluation::Evaluation;
+use inside_deep_learning_with_burn::inference::LoadError;
+use inside_deep_learning_with_burn::mlp_experiment::{self, MlpDataConfig, MlpExperimentConfig};
+use inside_deep_learning_with_burn::model::mlp::MlpConfig;
+use inside_deep_learning_with_burn::moons_data::data::{MoonDatasetConfig, MoonsConfig};
+use inside_deep_learning_with_burn::optimizer::OptimizerConfig;
+use inside_deep_learning_with_burn::split::SplitConfig;
+use inside_deep_learning_with_burn::train::TrainingConfig;
 
-use burn::{
-    backend::{wgpu::AutoGraphicsApi, Autodiff, Wgpu},
-    optim::AdamConfig,
-};
+struct MoonsClassification;
 
-fn main() {
-    type MoonsBackend = Wgpu<AutoGraphicsApi, f32, i32>;
-    type MoonsAutodiffBackend = Autodiff<MoonsBackend>;
+impl Example for MoonsClassification {
+    const ARTIFACT_DIR: &'static str = "examples/2-3-classification-problems/artifacts";
+
+    type Config = MlpExperimentConfig;
+
+    fn config() -> MlpExperimentConfig {
+        MlpExperimentConfig::new(
+            MlpConfig::new(2, vec![30, 30], 2),
+            TrainingConfig::new(OptimizerConfig::Adam(AdamConfig::new()))
+                .with_num_epochs(250)
+                .with_batch_size(32)
+                .with_num_workers(32),
+            MlpDataConfig::Moons(MoonDatasetConfig::new(
+                MoonsConfig::new(500, 500).with_noise(Some(0.01)),
+                SplitConfig::new(0.8, 0.1, 12, true),
+            )),
+        )
+    }
 
-    let device = burn::backend::wgpu::WgpuDevice::default();
-    let artifact_dir = "examples/2-3-classification-problems/artifacts";
+    fn train<B: AutodiffBackend>(
+        artifact_dir: &str,
+        config: MlpExperimentConfig,
+        device: B::Device,
+        checkpoint: Option<usize>,
+    ) {
+        mlp_experiment::train::<B>(artifact_dir, &config, device, checkpoint);
+    }
 
-    training::train::<MoonsAutodiffBackend>(
-        artifact_dir,
-        training::TrainingConfig::new(model::ModelConfig::new(2, 30, 2), AdamConfig::new()),
-        device.clone(),
-    );
+    fn infer<B: Backend>(artifact_dir: &str, device: B::Device, _index: Option<usize>) {
+        mlp_experiment::infer::<B>(artifact_dir, device);
+    }
 
-    inference::infer::<MoonsBackend>(artifact_dir, device);
+    fn evaluate<B: Backend>(
+        artifact_dir: &str,
+        device: B::Device,
+    ) -> Result<Evaluation, LoadError> {
+        mlp_experiment::evaluate::<B>(artifact_dir, device)
+    }
+}
+
+fn main() {
+    cli::run::<MoonsClassification>();
 }
@@ -1,30 +1,87 @@
-mod inference;
 mod model;
-mod training;
 
-use crate::model::ModelConfig;
-use burn::backend::{wgpu::AutoGraphicsApi, Autodiff, Wgpu};
-use burn::data::dataset::Dataset;
+use std::path::PathBuf;
+
+use burn::config::Config;
 use burn::optim::AdamConfig;
+use burn::tensor::backend::{AutodiffBackend, Backend};
+use inside_deep_learning_with_burn::cli::{self, Example};
+use inside_deep_learning_with_burn::evaluation::Evaluation;
+use inside_deep_learning_with_burn::image_experiment::{self, ImageDataConfig, ImageExperiment};
+use inside_deep_learning_with_burn::inference::LoadError;
+use inside_deep_learning_with_burn::model::cnn::CnnConfig;
+use inside_deep_learning_with_burn::optimizer::OptimizerConfig;
+use inside_deep_learning_with_burn::train::{ArtifactConfig, TrainingConfig};
+
+use crate::model::ModelConfig;
+
+#[derive(Config)]
+pub struct ExperimentConfig {
+    pub model: ModelConfig,
+    pub training: TrainingConfig,
+    #[config(default = "ImageDataConfig::new()")]
+    pub data: ImageDataConfig,
+}
+
+impl ArtifactConfig for ExperimentConfig {
+    fn training(&self) -> &TrainingConfig {
+        &self.training
+    }
+
+    fn dataset(&self) -> String {
+        self.data.description()
+    }
+
+    fn data_files(&self) -> Vec<PathBuf> {
+        self.data.files()
+    }
+}
+
+impl ImageExperiment for ExperimentConfig {
+    fn data(&self) -> &ImageDataConfig {
+        &self.data
+    }
+
+    fn cnn(&self) -> CnnConfig {
+        self.model.cnn(self.data.num_classes())
+    }
+}
+
+struct FirstCnn;
+
+impl Example for FirstCnn {
+    const ARTIFACT_DIR: &'static str = "examples/3-4-first-cnn/artifacts/";
+
+    type Config = ExperimentConfig;
+
+    fn config() -> ExperimentConfig {
+        ExperimentConfig::new(
+            ModelConfig::new(28, 28),
+            TrainingConfig::new(OptimizerConfig::Adam(AdamConfig::new())),
+        )
+    }
+
+    fn train<B: AutodiffBackend>(
+        artifact_dir: &str,
+        config: ExperimentConfig,
+        device: B::Device,
+        checkpoint: Option<usize>,
+    ) {
+        image_experiment::train::<B, _>(artifact_dir, &config, device, checkpoint);
+    }
+
+    fn infer<B: Backend>(artifact_dir: &str, device: B::Device, index: Option<usize>) {
+        image_experiment::infer::<B, ExperimentConfig>(artifact_dir, device, index.unwrap_or(42));
+    }
+
+    fn evaluate<B: Backend>(
+        artifact_dir: &str,
+        device: B::Device,
+    ) -> Result<Evaluation, LoadError> {
+        image_experiment::evaluate::<B, ExperimentConfig>(artifact_dir, device)
+    }
+}
 
 fn main() {
-    let artifact_dir = "examples/3-4-first-cnn/artifacts/";
-
-    type MyBackend = Wgpu<AutoGraphicsApi, f32, i32>;
-    type MyAutodiffBackend = Autodiff<MyBackend>;
-    let device = burn::backend::wgpu::WgpuDevice::default();
-
-    crate::training::train::<MyAutodiffBackend>(
-        artifact_dir,
-        crate::training::TrainingConfig::new(ModelConfig::new(10, 28, 28), AdamConfig::new()),
-        device.clone(),
-    );
-
-    crate::inference::infer::<MyBackend>(
-        artifact_dir,
-        device,
-        burn::data::dataset::vision::MnistDataset::test()
-            .get(42)
-            .unwrap(),
-    );
+    cli::run::<FirstCnn>();
 }
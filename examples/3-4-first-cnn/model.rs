@@ -1,47 +1,35 @@
-use burn::{
-    nn::{
-        conv::{Conv2d, Conv2dConfig},
-        Linear, LinearConfig,
-    },
-    prelude::*,
-};
-use nn::PaddingConfig2d;
-
-#[derive(Module, Debug)]
-pub struct Model<B: Backend> {
-    conv: Conv2d<B>,
-    linear: Linear<B>,
-}
+use burn::prelude::*;
+use inside_deep_learning_with_burn::model::cnn::{CnnConfig, ConvBlockConfig, Readout};
 
 #[derive(Config, Debug)]
 pub struct ModelConfig {
-    num_classes: usize,
     image_height: usize,
     image_width: usize,
+    /// Channels of the input images, e.g. 3 for RGB images.
+    #[config(default = 1)]
+    channels: usize,
+    #[config(default = "Readout::Flatten")]
+    readout: Readout,
+    /// Conv blocks replacing the single convolution, to try other architectures.
+    blocks: Option<Vec<ConvBlockConfig>>,
 }
 
 impl ModelConfig {
-    /// Returns the initialized model.
-    pub fn init<B: Backend>(&self, device: &B::Device) -> Model<B> {
-        Model {
-            conv: Conv2dConfig::new([1, 16], [3, 3])
-                .with_padding(PaddingConfig2d::Same)
-                .init(device),
-            linear: LinearConfig::new(16 * self.image_height * self.image_width, self.num_classes)
-                .init(device),
-        }
-    }
-}
-
-impl<B: Backend> Model<B> {
-    pub fn forward(&self, images: Tensor<B, 3>) -> Tensor<B, 2> {
-        let [batch_size, height, width] = images.dims();
-        // Create a channel at the second dimension.
-        let x = images.reshape([batch_size, 1, height, width]);
+    /// A single 3x3 convolution with 16 filters and tanh, then a linear classifier
+    /// over every pixel.
+    pub fn cnn(&self, num_classes: usize) -> CnnConfig {
+        let blocks = match &self.blocks {
+            Some(blocks) => blocks.clone(),
+            None => vec![ConvBlockConfig::new(16)],
+        };
 
-        let x = self.conv.forward(x); // [batch_size, 16, width, height]
-        let x = x.tanh();
-        let x = x.reshape([batch_size, 16 * height * width]);
-        self.linear.forward(x)
+        CnnConfig::new(
+            num_classes,
+            self.channels,
+            self.image_height,
+            self.image_width,
+            blocks,
+        )
+        .with_readout(self.readout)
     }
 }
@@ -1,29 +0,0 @@
-use burn::data::dataloader::batcher::Batcher;
-use burn::data::dataset::vision::MnistItem;
-use burn::{
-    config::Config,
-    module::Module,
-    record::{CompactRecorder, Recorder},
-    tensor::backend::Backend,
-};
-use inside_deep_learning_with_burn::mist_data::data::MnistBatcher;
-
-use crate::training::TrainingConfig;
-
-pub fn infer<B: Backend>(artifact_dir: &str, device: B::Device, item: MnistItem) {
-    let config = TrainingConfig::load(format!("{artifact_dir}/config.json"))
-        .expect("Config should exist for the model");
-    let record = CompactRecorder::new()
-        .load(format!("{artifact_dir}/model").into(), &device)
-        .expect("Trained model should exist");
-
-    let model = config.model.init::<B>(&device).load_record(record);
-
-    let label = item.label;
-    let batcher = MnistBatcher::new(device);
-    let batch = batcher.batch(vec![item]);
-    let output = model.forward(batch.images);
-    let predicted = output.argmax(1).flatten::<1>(0, 1).into_scalar();
-
-    println!("Predicted {} Expected {}", predicted, label);
-}
@@ -1,90 +1,58 @@
-use burn::{
-    nn::{
-        conv::{Conv2d, Conv2dConfig},
-        Linear, LinearConfig,
-    },
-    prelude::*,
-};
-use nn::{
-    pool::{MaxPool2d, MaxPool2dConfig},
-    PaddingConfig2d,
-};
-
-#[derive(Module, Debug)]
-pub struct Model<B: Backend> {
-    conv1: Conv2d<B>,
-    conv2: Conv2d<B>,
-    conv3: Conv2d<B>,
-    pool1: MaxPool2d,
-    conv4: Conv2d<B>,
-    conv5: Conv2d<B>,
-    conv6: Conv2d<B>,
-    pool2: MaxPool2d,
-    linear: Linear<B>,
-}
+use burn::prelude::*;
+use inside_deep_learning_with_burn::model::cnn::{CnnConfig, ConvBlockConfig, Pooling, Readout};
 
 #[derive(Config, Debug)]
 pub struct ModelConfig {
-    num_classes: usize,
+    /// Filters of the first stage, doubled by each following stage.
     filters: usize,
     image_height: usize,
     image_width: usize,
+    /// Channels of the input images, e.g. 3 for RGB images.
+    #[config(default = 1)]
+    channels: usize,
+    /// Pooling stages, each halving the image size rounded down.
+    #[config(default = 2)]
+    stages: usize,
+    /// Convolutions before each pooling, stages without any are skipped.
+    #[config(default = 3)]
+    convs_per_stage: usize,
+    #[config(default = "Readout::Flatten")]
+    readout: Readout,
+    /// Conv blocks replacing the stages, to try other architectures.
+    blocks: Option<Vec<ConvBlockConfig>>,
 }
 
 impl ModelConfig {
-    /// Returns the initialized model.
-    pub fn init<B: Backend>(&self, device: &B::Device) -> Model<B> {
-        Model {
-            conv1: Conv2dConfig::new([1, self.filters], [3, 3])
-                .with_padding(PaddingConfig2d::Same)
-                .init(device),
-            conv2: Conv2dConfig::new([self.filters, self.filters], [3, 3])
-                .with_padding(PaddingConfig2d::Same)
-                .init(device),
-            conv3: Conv2dConfig::new([self.filters, self.filters], [3, 3])
-                .with_padding(PaddingConfig2d::Same)
-                .init(device),
-            pool1: MaxPool2dConfig::new([2, 2]).with_strides([2, 2]).init(),
-            conv4: Conv2dConfig::new([self.filters, 2 * self.filters], [3, 3])
-                .with_padding(PaddingConfig2d::Same)
-                .init(device),
-            conv5: Conv2dConfig::new([2 * self.filters, 2 * self.filters], [3, 3])
-                .with_padding(PaddingConfig2d::Same)
-                .init(device),
-            conv6: Conv2dConfig::new([2 * self.filters, 2 * self.filters], [3, 3])
-                .with_padding(PaddingConfig2d::Same)
-                .init(device),
-            pool2: MaxPool2dConfig::new([2, 2]).with_strides([2, 2]).init(),
-            linear: LinearConfig::new(
-                2 * self.filters * (self.image_height / 4) * (self.image_width / 4),
-                self.num_classes,
-            )
-            .init(device),
-        }
-    }
-}
+    /// Stages of 3x3 convolutions with tanh, the last one of each stage followed by a
+    /// 2x2 max pooling, then a linear classifier over every pixel.
+    pub fn cnn(&self, num_classes: usize) -> CnnConfig {
+        let blocks = match &self.blocks {
+            Some(blocks) => blocks.clone(),
+            None => self.stages(),
+        };
 
-impl<B: Backend> Model<B> {
-    pub fn forward(&self, images: Tensor<B, 3>) -> Tensor<B, 2> {
-        let [batch_size, height, width] = images.dims();
-        // Create a channel at the second dimension.
-        let x = images.reshape([batch_size, 1, height, width]);
+        CnnConfig::new(
+            num_classes,
+            self.channels,
+            self.image_height,
+            self.image_width,
+            blocks,
+        )
+        .with_readout(self.readout)
+    }
 
-        let x = self.conv1.forward(x); // [batch_size, 16, width, height]
-        let x = x.tanh();
-        let x = self.conv2.forward(x); // [batch_size, 16, width, height]
-        let x = x.tanh();
-        let x = self.conv3.forward(x); // [batch_size, 16, width, height]
-        let x = x.tanh();
-        let x = self.pool1.forward(x); // [batch_size, 16, width / 2, height / 2]
-        let x = self.conv4.forward(x); // [batch_size, 2 * 16, width / 2, height / 2]
-        let x = x.tanh();
-        let x = self.conv5.forward(x); // [batch_size, 2 * 16, width / 2, height / 2]
-        let x = x.tanh();
-        let x = self.conv6.forward(x); // [batch_size, 2 * 16, width / 2, height / 2]
-        let x = x.tanh();
-        let x = self.pool2.forward(x); // [batch_size, 2 * 16, width / 4, height / 4]
-        let x = x.reshape([batch_size, 2 * 16 * (height / 4) * (width / 4)]);
-        self.linear.forward(x)
+    fn stages(&self) -> Vec<ConvBlockConfig> {
+        (0..self.stages)
+            .flat_map(|stage| {
+                let filters = self.filters << stage;
+                (0..self.convs_per_stage).map(move |conv| {
+                    let block = ConvBlockConfig::new(filters);
+                    match conv + 1 == self.convs_per_stage {
+                        true => block.with_pooling(Some(Pooling::Max { size: 2 })),
+                        false => block,
+                    }
+                })
+            })
+            .collect()
     }
 }
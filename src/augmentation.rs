@@ -0,0 +1,291 @@
+use burn::config::Config;
+use ndarray_rand::rand::rngs::StdRng;
+use ndarray_rand::rand::{Rng, SeedableRng};
+use ndarray_rand::rand_distr::{Distribution, Normal};
+
+use crate::runs::fnv1a;
+use crate::transform::Image;
+
+/// Random perturbation of a training image. Pixels moved in from outside of the
+/// image are filled with zeros, the background of MNIST-like datasets.
+#[derive(Config, Debug)]
+pub enum Augmentation {
+    /// Shifts the image by up to `max_shift` pixels along each axis.
+    Translate { max_shift: usize },
+    /// Rotates the image around its center by up to `max_degrees` either way.
+    Rotate { max_degrees: f32 },
+    /// With the given probability, sets a random rectangle covering between
+    /// `min_area` and `max_area` of the image to zero (Zhong et al., 2017).
+    Erase {
+        probability: f32,
+        min_area: f32,
+        max_area: f32,
+    },
+    /// Moves pixels along a random displacement field smoothed by a Gaussian of
+    /// width `sigma` and scaled by `alpha` (Simard et al., 2003).
+    Elastic { alpha: f32, sigma: f32 },
+    /// Adds Gaussian noise of standard deviation `std` to every pixel.
+    GaussianNoise { std: f32 },
+}
+
+/// Augmentations applied in order to each training image by the `MnistBatcher`,
+/// before its transforms.
+#[derive(Config, Debug)]
+pub struct AugmentationConfig {
+    pub augmentations: Vec<Augmentation>,
+    #[config(default = 42)]
+    pub seed: u64,
+}
+
+impl AugmentationConfig {
+    /// Random translations and rotations, i.e. the object movement pooling is robust to.
+    pub fn movement() -> Self {
+        Self::new(vec![
+            Augmentation::Translate { max_shift: 3 },
+            Augmentation::Rotate { max_degrees: 10.0 },
+        ])
+    }
+
+    /// Panics on parameters an augmentation cannot be sampled with.
+    pub fn init(&self) -> Augmenter {
+        for augmentation in &self.augmentations {
+            augmentation.check();
+        }
+
+        Augmenter {
+            augmentations: self.augmentations.clone(),
+            seed: self.seed,
+        }
+    }
+}
+
+/// Applies the augmentations of an [`AugmentationConfig`]. Every image gets its own
+/// generator, seeded from the config seed, a hash of the pixels of its batch and its
+/// index in the batch. The output only depends on the batch, not on the data loader
+/// worker batching it, and changes every epoch as the shuffling forms new batches.
+#[derive(Clone, Debug)]
+pub struct Augmenter {
+    augmentations: Vec<Augmentation>,
+    seed: u64,
+}
+
+impl Augmenter {
+    /// Augments the images of a batch.
+    pub fn apply_batch(&self, images: Vec<Image>) -> Vec<Image> {
+        let pixels: Vec<u8> = images
+            .iter()
+            .flat_map(|image| image.data.iter().flat_map(|pixel| pixel.to_le_bytes()))
+            .collect();
+        let batch = fnv1a(&pixels);
+
+        images
+            .into_iter()
+            .enumerate()
+            .map(|(index, image)| self.apply(image, index, batch))
+            .collect()
+    }
+
+    /// Augments the image at `index` of the batch whose pixels hash to `batch`.
+    pub fn apply(&self, image: Image, index: usize, batch: u64) -> Image {
+        let mut seed = [0; 32];
+        seed[..8].copy_from_slice(&self.seed.to_le_bytes());
+        seed[8..16].copy_from_slice(&(index as u64).to_le_bytes());
+        seed[16..24].copy_from_slice(&batch.to_le_bytes());
+        let mut rng = StdRng::from_seed(seed);
+
+        self.augmentations
+            .iter()
+            .fold(image, |image, augmentation| {
+                augmentation.apply(image, &mut rng)
+            })
+    }
+}
+
+impl Augmentation {
+    /// Perturbs the image with the parameters checked by [`AugmentationConfig::init`].
+    pub fn apply<R: Rng>(&self, image: Image, rng: &mut R) -> Image {
+        match self {
+            Augmentation::Translate { max_shift } => {
+                let max_shift = *max_shift as isize;
+                let dy = rng.gen_range(-max_shift..=max_shift) as f32;
+                let dx = rng.gen_range(-max_shift..=max_shift) as f32;
+                remap(&image, |y, x| (y - dy, x - dx))
+            }
+            Augmentation::Rotate { max_degrees } => {
+                let angle = rng.gen_range(-max_degrees..=*max_degrees).to_radians();
+                let (sin, cos) = angle.sin_cos();
+                let center_y = (image.height as f32 - 1.0) / 2.0;
+                let center_x = (image.width as f32 - 1.0) / 2.0;
+
+                // Each output pixel samples the input rotated the other way
+                remap(&image, |y, x| {
+                    let (y, x) = (y - center_y, x - center_x);
+                    (center_y - sin * x + cos * y, center_x + cos * x + sin * y)
+                })
+            }
+            Augmentation::Erase {
+                probability,
+                min_area,
+                max_area,
+            } => {
+                if rng.gen::<f32>() >= *probability {
+                    return image;
+                }
+                erase(image, *min_area, *max_area, rng)
+            }
+            Augmentation::Elastic { alpha, sigma } => {
+                let mut field = || {
+                    let field: Vec<f32> = (0..image.height * image.width)
+                        .map(|_| rng.gen_range(-1.0..=1.0))
+                        .collect();
+                    gaussian_blur(&field, image.height, image.width, *sigma)
+                        .into_iter()
+                        .map(|value| alpha * value)
+                        .collect::<Vec<_>>()
+                };
+                let (dy, dx) = (field(), field());
+
+                remap(&image, |y, x| {
+                    let i = y as usize * image.width + x as usize;
+                    (y + dy[i], x + dx[i])
+                })
+            }
+            Augmentation::GaussianNoise { std } => {
+                let normal = Normal::new(0.0, *std).unwrap();
+                Image {
+                    data: image
+                        .data
+                        .iter()
+                        .map(|pixel| pixel + normal.sample(&mut *rng))
+                        .collect(),
+                    ..image
+                }
+            }
+        }
+    }
+
+    fn check(&self) {
+        match self {
+            Augmentation::Translate { .. } => {}
+            Augmentation::Rotate { max_degrees } => assert!(
+                max_degrees.is_finite() && *max_degrees >= 0.0,
+                "Rotations need a non-negative max_degrees, got {max_degrees}"
+            ),
+            Augmentation::Erase {
+                probability,
+                min_area,
+                max_area,
+            } => {
+                assert!(
+                    (0.0..=1.0).contains(probability),
+                    "The erasing probability should be between 0 and 1, got {probability}"
+                );
+                assert!(
+                    0.0 <= *min_area && min_area <= max_area && *max_area <= 1.0,
+                    "The erased area should satisfy 0 <= min_area <= max_area <= 1, got {min_area} and {max_area}"
+                );
+            }
+            Augmentation::Elastic { alpha, sigma } => {
+                assert!(
+                    sigma.is_finite() && *sigma > 0.0,
+                    "The elastic displacement field needs a positive sigma, got {sigma}"
+                );
+                assert!(
+                    alpha.is_finite(),
+                    "The elastic displacement field needs a finite alpha, got {alpha}"
+                );
+            }
+            Augmentation::GaussianNoise { std } => assert!(
+                std.is_finite() && *std >= 0.0,
+                "Gaussian noise needs a non-negative std, got {std}"
+            ),
+        }
+    }
+}
+
+// Builds an image of the same shape where output pixel (y, x) is the bilinear sample of
+// the input at `source(y, x)`, or zero outside of the input
+fn remap(image: &Image, source: impl Fn(f32, f32) -> (f32, f32)) -> Image {
+    let mut output = Image::filled(image.channels, image.height, image.width, 0.0);
+    let pixel = |c: usize, y: isize, x: isize| {
+        if y < 0 || x < 0 || y >= image.height as isize || x >= image.width as isize {
+            0.0
+        } else {
+            image.get(c, y as usize, x as usize)
+        }
+    };
+
+    for y in 0..image.height {
+        for x in 0..image.width {
+            let (source_y, source_x) = source(y as f32, x as f32);
+            let (y0, x0) = (source_y.floor(), source_x.floor());
+            let (dy, dx) = (source_y - y0, source_x - x0);
+            let (y0, x0) = (y0 as isize, x0 as isize);
+
+            for c in 0..image.channels {
+                let top = pixel(c, y0, x0) * (1.0 - dx) + pixel(c, y0, x0 + 1) * dx;
+                let bottom = pixel(c, y0 + 1, x0) * (1.0 - dx) + pixel(c, y0 + 1, x0 + 1) * dx;
+                output.set(c, y, x, top * (1.0 - dy) + bottom * dy);
+            }
+        }
+    }
+    output
+}
+
+fn erase<R: Rng>(mut image: Image, min_area: f32, max_area: f32, rng: &mut R) -> Image {
+    let area = (image.height * image.width) as f32 * rng.gen_range(min_area..=max_area);
+    // Aspect ratios from the random erasing paper, sampled uniformly in log space
+    let ratio = rng.gen_range(0.3f32.ln()..=(1.0f32 / 0.3).ln()).exp();
+
+    let height = ((area * ratio).sqrt().round() as usize).clamp(1, image.height);
+    let width = ((area / ratio).sqrt().round() as usize).clamp(1, image.width);
+    let top = rng.gen_range(0..=image.height - height);
+    let left = rng.gen_range(0..=image.width - width);
+
+    for c in 0..image.channels {
+        for y in top..top + height {
+            for x in left..left + width {
+                image.set(c, y, x, 0.0);
+            }
+        }
+    }
+    image
+}
+
+// Separable Gaussian blur of a single channel, truncated at three standard deviations
+// and repeating the border pixels
+fn gaussian_blur(values: &[f32], height: usize, width: usize, sigma: f32) -> Vec<f32> {
+    let radius = (3.0 * sigma).ceil() as isize;
+    let kernel: Vec<f32> = (-radius..=radius)
+        .map(|i| (-(i * i) as f32 / (2.0 * sigma * sigma)).exp())
+        .collect();
+    let total: f32 = kernel.iter().sum();
+
+    // Convolves the `len` values found at `at(0), ..., at(len - 1)`
+    let convolve = |at: &dyn Fn(usize) -> f32, i: usize, len: usize| {
+        kernel
+            .iter()
+            .enumerate()
+            .map(|(k, weight)| {
+                let j = (i as isize + k as isize - radius).clamp(0, len as isize - 1);
+                weight * at(j as usize)
+            })
+            .sum::<f32>()
+            / total
+    };
+
+    let mut rows = vec![0.0; values.len()];
+    for y in 0..height {
+        for x in 0..width {
+            rows[y * width + x] = convolve(&|j| values[y * width + j], x, width);
+        }
+    }
+
+    let mut output = vec![0.0; values.len()];
+    for y in 0..height {
+        for x in 0..width {
+            output[y * width + x] = convolve(&|j| rows[j * width + x], y, height);
+        }
+    }
+    output
+}
@@ -0,0 +1,139 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use burn::tensor::backend::AutodiffBackend;
+
+#[cfg(not(any(feature = "ndarray", feature = "tch", feature = "wgpu")))]
+compile_error!("At least one backend feature should be enabled: ndarray, tch or wgpu");
+
+/// Environment variable read when no `--backend` flag is given.
+pub const BACKEND_ENV: &str = "BURN_BACKEND";
+
+/// Backends the examples can run on. Each one is only available when the crate is
+/// built with the matching cargo feature (`ndarray`, `tch` or `wgpu`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BackendKind {
+    NdArray,
+    LibTorchCpu,
+    Wgpu,
+}
+
+impl BackendKind {
+    /// Parses the backend given on the command line, falling back to the
+    /// `BURN_BACKEND` environment variable and then to the default backend. Backends
+    /// whose feature is not enabled are rejected.
+    pub fn from_flag(flag: Option<String>) -> Result<Self, String> {
+        let kind = match flag.or_else(|| std::env::var(BACKEND_ENV).ok()) {
+            Some(name) => name.parse()?,
+            None => Self::default_kind().ok_or_else(|| {
+                format!("No backend is compiled in, expected {}", Self::expected())
+            })?,
+        };
+
+        match kind.is_enabled() {
+            true => Ok(kind),
+            false => Err(format!(
+                "Backend {kind} is not compiled in, expected {}",
+                Self::expected()
+            )),
+        }
+    }
+
+    /// The first backend compiled in among ndarray, libtorch-cpu and wgpu, so that the
+    /// examples run without a GPU unless one is asked for.
+    pub fn default_kind() -> Option<Self> {
+        Self::enabled().into_iter().next()
+    }
+
+    /// Backends whose cargo feature is enabled.
+    pub fn enabled() -> Vec<Self> {
+        [
+            BackendKind::NdArray,
+            BackendKind::LibTorchCpu,
+            BackendKind::Wgpu,
+        ]
+        .into_iter()
+        .filter(BackendKind::is_enabled)
+        .collect()
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        match self {
+            BackendKind::NdArray => cfg!(feature = "ndarray"),
+            BackendKind::LibTorchCpu => cfg!(feature = "tch"),
+            BackendKind::Wgpu => cfg!(feature = "wgpu"),
+        }
+    }
+
+    // Names of the compiled backends for error messages, e.g. `ndarray or wgpu`
+    fn expected() -> String {
+        let names: Vec<String> = Self::enabled().iter().map(ToString::to_string).collect();
+        match names.split_last() {
+            Some((last, [])) => last.clone(),
+            Some((last, names)) => format!("{} or {last}", names.join(", ")),
+            None => String::from("a backend feature to be enabled"),
+        }
+    }
+}
+
+impl FromStr for BackendKind {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.to_lowercase().as_str() {
+            "ndarray" => Ok(BackendKind::NdArray),
+            "tch" | "libtorch" | "libtorch-cpu" => Ok(BackendKind::LibTorchCpu),
+            "wgpu" => Ok(BackendKind::Wgpu),
+            _ => Err(format!(
+                "Unknown backend {name}, expected {}",
+                BackendKind::expected()
+            )),
+        }
+    }
+}
+
+impl Display for BackendKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            BackendKind::NdArray => "ndarray",
+            BackendKind::LibTorchCpu => "libtorch-cpu",
+            BackendKind::Wgpu => "wgpu",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Work generic over the backend, e.g. training followed by inference. Implemented
+/// by each example so that [`run`] can pick the concrete backend at runtime.
+pub trait Experiment {
+    fn run<B: AutodiffBackend>(self, device: B::Device);
+}
+
+/// Runs the experiment on the selected backend and its default device.
+pub fn run<E: Experiment>(kind: BackendKind, experiment: E) {
+    assert!(
+        kind.is_enabled(),
+        "Backend {kind} is not enabled, rebuild with the matching cargo feature"
+    );
+
+    match kind {
+        BackendKind::NdArray => {
+            #[cfg(feature = "ndarray")]
+            experiment.run::<burn::backend::Autodiff<burn::backend::NdArray<f32>>>(
+                burn::backend::ndarray::NdArrayDevice::Cpu,
+            );
+        }
+        BackendKind::LibTorchCpu => {
+            #[cfg(feature = "tch")]
+            experiment.run::<burn::backend::Autodiff<burn::backend::LibTorch<f32>>>(
+                burn::backend::libtorch::LibTorchDevice::Cpu,
+            );
+        }
+        BackendKind::Wgpu => {
+            #[cfg(feature = "wgpu")]
+            experiment.run::<burn::backend::Autodiff<
+                burn::backend::Wgpu<burn::backend::wgpu::AutoGraphicsApi, f32, i32>,
+            >>(burn::backend::wgpu::WgpuDevice::default());
+        }
+    }
+}
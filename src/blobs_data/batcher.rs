@@ -0,0 +1,41 @@
+use burn::{
+    data::dataloader::batcher::Batcher,
+    tensor::{backend::Backend, Data, Int, Tensor},
+};
+
+use super::data::BlobsItem;
+use crate::moons_data::batcher::MoonsBatch;
+
+/// Batches the blob points into a [`MoonsBatch`], which the MLP classifier trains on.
+#[derive(Clone)]
+pub struct BlobsBatcher<B: Backend> {
+    device: B::Device,
+}
+
+impl<B: Backend> BlobsBatcher<B> {
+    pub fn new(device: B::Device) -> Self {
+        Self { device }
+    }
+}
+
+impl<B: Backend> Batcher<BlobsItem, MoonsBatch<B>> for BlobsBatcher<B> {
+    fn batch(&self, items: Vec<BlobsItem>) -> MoonsBatch<B> {
+        let x = items
+            .iter()
+            .map(|item| Data::<f32, 2>::from([item.x]))
+            .map(|data| Tensor::<B, 2>::from_data(data.convert(), &self.device))
+            .collect();
+
+        let y = items
+            .iter()
+            .map(|item| item.y)
+            .map(|y| Data::<i8, 1>::from([y]))
+            .map(|data| Tensor::<B, 1, Int>::from_data(data.convert(), &self.device))
+            .collect();
+
+        let x = Tensor::cat(x, 0).to_device(&self.device);
+        let y = Tensor::cat(y, 0).to_device(&self.device);
+
+        MoonsBatch { x, y }
+    }
+}
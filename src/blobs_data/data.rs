@@ -0,0 +1,125 @@
+use burn::config::Config;
+use burn::data::dataset::Dataset;
+use ndarray_rand::rand::rngs::StdRng;
+use ndarray_rand::rand::seq::SliceRandom;
+use ndarray_rand::rand::{Rng, SeedableRng};
+use ndarray_rand::rand_distr::{Distribution, Normal};
+
+use crate::split::{Split, SplitConfig};
+
+/// Isotropic gaussian blobs, one per class, whose centers are drawn uniformly from
+/// `center_box` like `sklearn.datasets.make_blobs`.
+#[derive(Config, Debug)]
+pub struct BlobsConfig {
+    pub n_samples: usize,
+    pub n_classes: usize,
+    #[config(default = 1.0)]
+    pub cluster_std: f64,
+    #[config(default = "(-10.0, 10.0)")]
+    pub center_box: (f64, f64),
+    #[config(default = true)]
+    pub shuffle: bool,
+    #[config(default = 21)]
+    pub seed: u64,
+}
+
+impl BlobsConfig {
+    pub fn centers(&self) -> Vec<[f64; 2]> {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        self.sample_centers(&mut rng)
+    }
+
+    fn sample_centers(&self, rng: &mut StdRng) -> Vec<[f64; 2]> {
+        let (low, high) = self.center_box;
+
+        (0..self.n_classes)
+            .map(|_| [rng.gen_range(low..high), rng.gen_range(low..high)])
+            .collect()
+    }
+
+    pub fn generate(&self) -> Vec<BlobsItem> {
+        assert!(
+            (1..=i8::MAX as usize).contains(&self.n_classes),
+            "Between 1 and {} classes are supported, got {}",
+            i8::MAX,
+            self.n_classes
+        );
+
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let centers = self.sample_centers(&mut rng);
+
+        let normal = Normal::<f64>::new(0.0, self.cluster_std).unwrap();
+
+        // The first classes get one extra sample when n_samples is not a multiple
+        let mut items: Vec<BlobsItem> = centers
+            .iter()
+            .enumerate()
+            .flat_map(|(class, center)| {
+                let n = self.n_samples / self.n_classes
+                    + usize::from(class < self.n_samples % self.n_classes);
+                (0..n).map(move |_| (class, *center)).collect::<Vec<_>>()
+            })
+            .map(|(class, [c1, c2])| BlobsItem {
+                x: [
+                    (c1 + normal.sample(&mut rng)) as f32,
+                    (c2 + normal.sample(&mut rng)) as f32,
+                ],
+                y: class as i8,
+            })
+            .collect();
+
+        if self.shuffle {
+            items.shuffle(&mut rng);
+        }
+
+        items
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct BlobsItem {
+    pub x: [f32; 2],
+    pub y: i8,
+}
+
+pub struct BlobsDataset {
+    dataset: Vec<BlobsItem>,
+}
+
+impl Dataset<BlobsItem> for BlobsDataset {
+    fn get(&self, index: usize) -> Option<BlobsItem> {
+        self.dataset.get(index).cloned()
+    }
+
+    fn len(&self) -> usize {
+        self.dataset.len()
+    }
+}
+
+pub struct BlobsDatasetConfig {
+    pub blobs: BlobsConfig,
+    pub split: SplitConfig,
+}
+
+impl BlobsDatasetConfig {
+    pub fn train(&self) -> BlobsDataset {
+        self.dataset(Split::Train)
+    }
+
+    pub fn valid(&self) -> BlobsDataset {
+        self.dataset(Split::Valid)
+    }
+
+    pub fn test(&self) -> BlobsDataset {
+        self.dataset(Split::Test)
+    }
+
+    fn dataset(&self, split: Split) -> BlobsDataset {
+        let items = self.blobs.generate();
+        let splits = self.split.split_by(items, |item| item.y);
+
+        BlobsDataset {
+            dataset: splits.take(split),
+        }
+    }
+}
@@ -0,0 +1,174 @@
+use std::fmt::Display;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use burn::data::dataset::Dataset;
+
+use crate::split::Split;
+use crate::transform::Image;
+
+const TRAIN_FILES: [&str; 5] = [
+    "data_batch_1.bin",
+    "data_batch_2.bin",
+    "data_batch_3.bin",
+    "data_batch_4.bin",
+    "data_batch_5.bin",
+];
+const TEST_FILES: [&str; 1] = ["test_batch.bin"];
+
+pub const CHANNELS: usize = 3;
+pub const HEIGHT: usize = 32;
+pub const WIDTH: usize = 32;
+pub const NUM_CLASSES: usize = 10;
+
+// Each record is a label byte followed by the red, green then blue planes of the image
+const RECORD_SIZE: usize = 1 + CHANNELS * HEIGHT * WIDTH;
+
+#[derive(Debug)]
+pub enum CifarError {
+    MissingFile {
+        path: PathBuf,
+    },
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    Truncated {
+        path: PathBuf,
+        size: usize,
+    },
+    BadLabel {
+        path: PathBuf,
+        index: usize,
+        label: u8,
+    },
+    /// CIFAR-10 only comes with training and test files.
+    NoSplit {
+        split: Split,
+    },
+}
+
+impl Display for CifarError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CifarError::MissingFile { path } => write!(f, "{} was not found", path.display()),
+            CifarError::Io { path, source } => {
+                write!(f, "Could not read {}: {source}", path.display())
+            }
+            CifarError::Truncated { path, size } => write!(
+                f,
+                "Truncated data in {}: {size} bytes is not a multiple of the {RECORD_SIZE} bytes records",
+                path.display()
+            ),
+            CifarError::BadLabel { path, index, label } => write!(
+                f,
+                "Bad label {label} for record {index} of {}, expected less than {NUM_CLASSES}",
+                path.display()
+            ),
+            CifarError::NoSplit { split } => write!(f, "CIFAR-10 has no {split} split"),
+        }
+    }
+}
+
+impl std::error::Error for CifarError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CifarError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Image of CIFAR-10 with pixels in `[0, 255]`, of shape `[3, 32, 32]`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CifarItem {
+    pub image: Image,
+    pub label: u8,
+}
+
+/// CIFAR-10 read from the binary version of the dataset, i.e. the `data_batch_*.bin`
+/// and `test_batch.bin` files of https://www.cs.toronto.edu/~kriz/cifar-10-binary.tar.gz.
+pub struct CifarDataset {
+    dataset: Vec<CifarItem>,
+}
+
+impl Dataset<CifarItem> for CifarDataset {
+    fn get(&self, index: usize) -> Option<CifarItem> {
+        self.dataset.get(index).cloned()
+    }
+
+    fn len(&self) -> usize {
+        self.dataset.len()
+    }
+}
+
+impl CifarDataset {
+    /// Reads the 50,000 training images of the five `data_batch_*.bin` files in `dir`.
+    pub fn train(dir: impl AsRef<Path>) -> Result<Self, CifarError> {
+        Self::from_dir(dir, &TRAIN_FILES)
+    }
+
+    /// Reads the 10,000 test images of `test_batch.bin` in `dir`.
+    pub fn test(dir: impl AsRef<Path>) -> Result<Self, CifarError> {
+        Self::from_dir(dir, &TEST_FILES)
+    }
+
+    /// Reads the training or test split from `dir`.
+    pub fn split(dir: impl AsRef<Path>, split: Split) -> Result<Self, CifarError> {
+        match split {
+            Split::Train => Self::train(dir),
+            Split::Test => Self::test(dir),
+            Split::Valid => Err(CifarError::NoSplit { split }),
+        }
+    }
+
+    /// Reads the records of the given files in `dir`, in order.
+    pub fn from_dir(dir: impl AsRef<Path>, files: &[&str]) -> Result<Self, CifarError> {
+        let mut dataset = Vec::new();
+        for file in files {
+            dataset.extend(read_file(&dir.as_ref().join(file))?);
+        }
+
+        Ok(Self { dataset })
+    }
+}
+
+fn read_file(path: &Path) -> Result<Vec<CifarItem>, CifarError> {
+    if !path.is_file() {
+        return Err(CifarError::MissingFile {
+            path: path.to_path_buf(),
+        });
+    }
+
+    let bytes = fs::read(path).map_err(|source| CifarError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    if bytes.len() % RECORD_SIZE != 0 {
+        return Err(CifarError::Truncated {
+            path: path.to_path_buf(),
+            size: bytes.len(),
+        });
+    }
+
+    bytes
+        .chunks_exact(RECORD_SIZE)
+        .enumerate()
+        .map(|(index, record)| {
+            let label = record[0];
+            if label as usize >= NUM_CLASSES {
+                return Err(CifarError::BadLabel {
+                    path: path.to_path_buf(),
+                    index,
+                    label,
+                });
+            }
+
+            let pixels = record[1..].iter().map(|pixel| *pixel as f32).collect();
+            Ok(CifarItem {
+                image: Image::new(CHANNELS, HEIGHT, WIDTH, pixels),
+                label,
+            })
+        })
+        .collect()
+}
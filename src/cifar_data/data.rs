@@ -0,0 +1,29 @@
+use crate::transform::{ImageStatistics, Transform, TransformPipeline};
+
+pub const CLASSES: [&str; 10] = [
+    "airplane",
+    "automobile",
+    "bird",
+    "cat",
+    "deer",
+    "dog",
+    "frog",
+    "horse",
+    "ship",
+    "truck",
+];
+
+/// Mean and standard deviation of the red, green and blue channels of the training
+/// images scaled to `[0, 1]`.
+pub fn statistics() -> ImageStatistics {
+    ImageStatistics::new(vec![0.4914, 0.4822, 0.4465], vec![0.2470, 0.2435, 0.2616])
+}
+
+/// Scales the pixels to `[0, 1]` then standardizes each channel with [`statistics`],
+/// the transform of the `MnistBatcher` batching CIFAR-10.
+pub fn transform() -> TransformPipeline {
+    TransformPipeline::new(vec![Transform::Scale {
+        factor: 1.0 / 255.0,
+    }])
+    .normalize(&statistics())
+}
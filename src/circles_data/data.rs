@@ -0,0 +1,143 @@
+use burn::config::Config;
+use burn::data::dataset::Dataset;
+use ndarray::{concatenate, stack, Array, Axis, Ix1, Ix2};
+use ndarray_rand::rand::seq::SliceRandom;
+use ndarray_rand::rand::{self, SeedableRng};
+use ndarray_rand::rand_distr::{Distribution, Normal};
+use std::f64::consts::PI;
+
+use crate::split::{Split, SplitConfig};
+
+/// A large circle (class 0) containing a smaller one (class 1), following the
+/// semantics of `sklearn.datasets.make_circles`.
+#[derive(Config, Debug)]
+pub struct CirclesConfig {
+    pub n_samples_out: usize,
+    pub n_samples_in: usize,
+    pub noise: Option<f64>,
+    /// Scale factor between the inner and the outer circle.
+    #[config(default = 0.8)]
+    pub factor: f64,
+    #[config(default = true)]
+    pub shuffle: bool,
+    #[config(default = 21)]
+    pub seed: u64,
+}
+
+impl CirclesConfig {
+    /// Returns the points as a `[n_samples, 2]` array together with their classes.
+    pub fn arrays(&self) -> (Array<f64, Ix2>, Array<i8, Ix1>) {
+        let outer = angles(self.n_samples_out);
+        let inner = angles(self.n_samples_in);
+
+        let x1 = concatenate(
+            Axis(0),
+            &[
+                outer.mapv(f64::cos).view(),
+                inner.mapv(|angle| self.factor * angle.cos()).view(),
+            ],
+        )
+        .unwrap();
+        let x2 = concatenate(
+            Axis(0),
+            &[
+                outer.mapv(f64::sin).view(),
+                inner.mapv(|angle| self.factor * angle.sin()).view(),
+            ],
+        )
+        .unwrap();
+        let mut x: Array<f64, Ix2> = stack(Axis(1), &[x1.view(), x2.view()]).unwrap();
+        let mut y: Array<i8, Ix1> = concatenate(
+            Axis(0),
+            &[
+                Array::<i8, Ix1>::zeros(self.n_samples_out).view(),
+                Array::<i8, Ix1>::ones(self.n_samples_in).view(),
+            ],
+        )
+        .unwrap();
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(self.seed);
+
+        if self.shuffle {
+            let mut indices: Vec<usize> = (0..y.len()).collect();
+            indices.shuffle(&mut rng);
+            x = x.select(Axis(0), &indices);
+            y = y.select(Axis(0), &indices);
+        }
+
+        if let Some(noise) = self.noise {
+            let normal = Normal::<f64>::new(0.0, noise).unwrap();
+            x.iter_mut()
+                .for_each(|value| *value += normal.sample(&mut rng));
+        }
+
+        (x, y)
+    }
+
+    pub fn generate(&self) -> Vec<CirclesItem> {
+        let (x, y) = self.arrays();
+
+        x.axis_iter(Axis(0))
+            .zip(y)
+            .map(|(x, y)| CirclesItem {
+                x: [x[0] as f32, x[1] as f32],
+                y,
+            })
+            .collect()
+    }
+}
+
+// Evenly spaced angles in [0, 2 * PI), the end point is excluded so that no point is
+// repeated
+fn angles(n: usize) -> Array<f64, Ix1> {
+    let step = 2.0 * PI / n.max(1) as f64;
+    Array::from_iter((0..n).map(|i| i as f64 * step))
+}
+
+#[derive(Clone, Debug)]
+pub struct CirclesItem {
+    pub x: [f32; 2],
+    pub y: i8,
+}
+
+pub struct CirclesDataset {
+    dataset: Vec<CirclesItem>,
+}
+
+impl Dataset<CirclesItem> for CirclesDataset {
+    fn get(&self, index: usize) -> Option<CirclesItem> {
+        self.dataset.get(index).cloned()
+    }
+
+    fn len(&self) -> usize {
+        self.dataset.len()
+    }
+}
+
+pub struct CirclesDatasetConfig {
+    pub circles: CirclesConfig,
+    pub split: SplitConfig,
+}
+
+impl CirclesDatasetConfig {
+    pub fn train(&self) -> CirclesDataset {
+        self.dataset(Split::Train)
+    }
+
+    pub fn valid(&self) -> CirclesDataset {
+        self.dataset(Split::Valid)
+    }
+
+    pub fn test(&self) -> CirclesDataset {
+        self.dataset(Split::Test)
+    }
+
+    fn dataset(&self, split: Split) -> CirclesDataset {
+        let items = self.circles.generate();
+        let splits = self.split.split_by(items, |item| item.y);
+
+        CirclesDataset {
+            dataset: splits.take(split),
+        }
+    }
+}
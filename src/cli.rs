@@ -0,0 +1,360 @@
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use burn::{
+    config::Config,
+    tensor::backend::{AutodiffBackend, Backend},
+};
+
+use crate::backend::{self, BackendKind, Experiment, BACKEND_ENV};
+use crate::evaluation::Evaluation;
+use crate::inference::{load_config, LoadError};
+use crate::overrides::{self, OverrideError};
+use crate::runs::{latest_run_dir, new_run_dir, RunReport};
+use crate::sweep::{run_sweep, SweepConfig};
+use crate::train::{latest_checkpoint, ArtifactConfig};
+
+pub const USAGE: &str = "\
+Usage: <example> [COMMAND] [OPTIONS]
+
+Commands:
+  train       Train a new model in a new run directory
+  resume      Continue training from the latest checkpoint of the run
+  infer       Run inference with the trained model of the run
+  evaluate    Compute the metrics of the trained model of the run on the test set
+  report      Compare every run in a table, report.csv and report.html
+  sweep       Train one run per set of hyperparameters of the --sweep file
+  (none)      Train then infer
+
+Options:
+  --artifact-dir <DIR>     Directory of the runs, each with its config, manifest,
+                           checkpoints and trained model
+  --run <ID>               Run used by resume, infer and evaluate (default: latest)
+  --backend <NAME>         ndarray, libtorch-cpu or wgpu (default: $BURN_BACKEND,
+                           then ndarray when compiled in)
+  --config <FILE>          JSON file overriding the default config, may be partial
+  --set <KEY=VALUE>        Override a config field by its dotted path, e.g.
+                           --set training.optimizer.Adam.beta_1=0.8
+  --epochs <N>             Same as --set training.num_epochs=<N>
+  --batch-size <N>         Same as --set training.batch_size=<N>
+  --num-workers <N>        Same as --set training.num_workers=<N>
+  --learning-rate <LR>     Same as --set training.learning_rate=<LR>
+  --seed <N>               Same as --set training.seed=<N>
+  --index <N>              Test item used by infer, for examples predicting one item
+  --sweep <FILE>           JSON file of the grid or random search run by sweep
+  --help                   Print this message";
+
+// Shortcut flags and the config field they override
+const TRAINING_FLAGS: [(&str, &str); 5] = [
+    ("--epochs", "training.num_epochs"),
+    ("--batch-size", "training.batch_size"),
+    ("--num-workers", "training.num_workers"),
+    ("--learning-rate", "training.learning_rate"),
+    ("--seed", "training.seed"),
+];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Command {
+    /// Trains then runs inference, like the examples did before having commands.
+    #[default]
+    Run,
+    Train,
+    Resume,
+    Infer,
+    Evaluate,
+    Report,
+    Sweep,
+}
+
+impl FromStr for Command {
+    type Err = CliError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "train" => Ok(Command::Train),
+            "resume" => Ok(Command::Resume),
+            "infer" => Ok(Command::Infer),
+            "evaluate" => Ok(Command::Evaluate),
+            "report" => Ok(Command::Report),
+            "sweep" => Ok(Command::Sweep),
+            _ => Err(CliError::UnknownCommand(name.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CliError {
+    HelpRequested,
+    UnknownCommand(String),
+    UnknownFlag(String),
+    MissingValue {
+        flag: String,
+    },
+    InvalidValue {
+        flag: String,
+        value: String,
+        message: String,
+    },
+}
+
+impl Display for CliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CliError::HelpRequested => f.write_str(USAGE),
+            CliError::UnknownCommand(name) => write!(f, "Unknown command {name}"),
+            CliError::UnknownFlag(flag) => write!(f, "Unknown option {flag}"),
+            CliError::MissingValue { flag } => write!(f, "Option {flag} expects a value"),
+            CliError::InvalidValue {
+                flag,
+                value,
+                message,
+            } => write!(f, "Invalid value {value:?} for {flag}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+/// Arguments shared by the examples, see [`USAGE`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cli {
+    pub command: Command,
+    pub artifact_dir: Option<String>,
+    pub run: Option<String>,
+    pub backend: BackendKind,
+    pub config: Option<PathBuf>,
+    /// Dotted config paths and their values, applied in order after `config`.
+    pub overrides: Vec<(String, String)>,
+    pub index: Option<usize>,
+    pub sweep: Option<PathBuf>,
+}
+
+impl Cli {
+    /// Parses the process arguments, exiting with the usage on errors.
+    pub fn from_args() -> Self {
+        match Self::parse(std::env::args().skip(1)) {
+            Ok(cli) => cli,
+            Err(CliError::HelpRequested) => {
+                println!("{USAGE}");
+                std::process::exit(0);
+            }
+            Err(err) => {
+                eprintln!("{err}\n\n{USAGE}");
+                std::process::exit(2);
+            }
+        }
+    }
+
+    /// Parses arguments, not including the program name.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, CliError> {
+        let mut args = args.into_iter();
+        let mut command = None;
+        let mut artifact_dir = None;
+        let mut run = None;
+        let mut backend = None;
+        let mut config = None;
+        let mut overrides = Vec::new();
+        let mut index = None;
+        let mut sweep = None;
+
+        while let Some(arg) = args.next() {
+            if !arg.starts_with("--") {
+                match command {
+                    None => command = Some(arg.parse()?),
+                    Some(_) => return Err(CliError::UnknownCommand(arg)),
+                }
+                continue;
+            }
+
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+                None => (arg, None),
+            };
+
+            if flag == "--help" {
+                return Err(CliError::HelpRequested);
+            }
+
+            let value = inline
+                .or_else(|| args.next())
+                .ok_or_else(|| CliError::MissingValue { flag: flag.clone() })?;
+            let invalid = |message: String| CliError::InvalidValue {
+                flag: flag.clone(),
+                value: value.clone(),
+                message,
+            };
+
+            match flag.as_str() {
+                "--artifact-dir" => artifact_dir = Some(value),
+                "--run" => run = Some(value),
+                "--backend" => backend = Some(value),
+                "--config" => config = Some(PathBuf::from(value)),
+                "--set" => {
+                    let (key, value) = value
+                        .split_once('=')
+                        .ok_or_else(|| invalid("expected KEY=VALUE".to_string()))?;
+                    overrides.push((key.to_string(), value.to_string()));
+                }
+                "--sweep" => sweep = Some(PathBuf::from(value)),
+                "--index" => index = Some(value.parse().map_err(|err| invalid(format!("{err}")))?),
+                _ => match TRAINING_FLAGS.iter().find(|(name, _)| *name == flag) {
+                    Some((_, key)) => overrides.push((key.to_string(), value)),
+                    None => return Err(CliError::UnknownFlag(flag)),
+                },
+            }
+        }
+
+        let backend =
+            BackendKind::from_flag(backend.clone()).map_err(|message| CliError::InvalidValue {
+                flag: "--backend".to_string(),
+                value: backend
+                    .or_else(|| std::env::var(BACKEND_ENV).ok())
+                    .unwrap_or_default(),
+                message,
+            })?;
+
+        Ok(Self {
+            command: command.unwrap_or_default(),
+            artifact_dir,
+            run,
+            backend,
+            config,
+            overrides,
+            index,
+            sweep,
+        })
+    }
+
+    /// Applies the `--config` file then the overrides on top of `config`.
+    pub fn apply<C: Config>(&self, config: C) -> Result<C, OverrideError> {
+        let config = match &self.config {
+            Some(path) => overrides::merge_file(&config, path)?,
+            None => config,
+        };
+
+        overrides::apply(&config, &self.overrides)
+    }
+}
+
+/// An example that can be trained, resumed, evaluated and used for inference from
+/// the command line with [`run`].
+pub trait Example {
+    /// Directory of the runs used when `--artifact-dir` is not given.
+    const ARTIFACT_DIR: &'static str;
+
+    type Config: ArtifactConfig;
+
+    /// Config used for training before the command line overrides.
+    fn config() -> Self::Config;
+
+    fn train<B: AutodiffBackend>(
+        artifact_dir: &str,
+        config: Self::Config,
+        device: B::Device,
+        checkpoint: Option<usize>,
+    );
+
+    fn infer<B: Backend>(artifact_dir: &str, device: B::Device, index: Option<usize>);
+
+    fn evaluate<B: Backend>(artifact_dir: &str, device: B::Device)
+        -> Result<Evaluation, LoadError>;
+}
+
+/// Parses the command line and runs the selected command of the example.
+pub fn run<E: Example>() {
+    let cli = Cli::from_args();
+
+    if cli.command == Command::Report {
+        let runs_dir = cli.artifact_dir.as_deref().unwrap_or(E::ARTIFACT_DIR);
+        let report = RunReport::load(runs_dir).unwrap_or_else(exit);
+
+        print!("{report}");
+        return report.write(runs_dir).unwrap_or_else(exit);
+    }
+
+    backend::run(
+        cli.backend,
+        Runner::<E> {
+            cli,
+            example: PhantomData,
+        },
+    );
+}
+
+struct Runner<E> {
+    cli: Cli,
+    example: PhantomData<E>,
+}
+
+impl<E: Example> Experiment for Runner<E> {
+    fn run<B: AutodiffBackend>(self, device: B::Device) {
+        let cli = self.cli;
+        let runs_dir = cli.artifact_dir.as_deref().unwrap_or(E::ARTIFACT_DIR);
+        let run_dir = || match &cli.run {
+            Some(id) => format!("{}/{id}", runs_dir.trim_end_matches('/')),
+            None => latest_run_dir(runs_dir).unwrap_or_else(exit),
+        };
+
+        match cli.command {
+            Command::Run | Command::Train => {
+                let config = cli.apply(E::config()).unwrap_or_else(exit);
+                let artifact_dir = &new_run_dir(runs_dir, &config).unwrap_or_else(exit);
+                E::train::<B>(artifact_dir, config, device.clone(), None);
+
+                if cli.command == Command::Run {
+                    E::infer::<B::InnerBackend>(artifact_dir, device, cli.index);
+                }
+            }
+            Command::Resume => {
+                let artifact_dir = &run_dir();
+                let checkpoint = latest_checkpoint(artifact_dir).unwrap_or_else(|| {
+                    exit(format!(
+                        "No checkpoint was found in {artifact_dir}/checkpoint"
+                    ))
+                });
+                let config = load_config::<E::Config>(artifact_dir).unwrap_or_else(exit);
+                let saved = config.training().optimizer.clone();
+                let config = cli.apply(config).unwrap_or_else(exit);
+                let optimizer = &config.training().optimizer;
+                optimizer.check_resume(&saved).unwrap_or_else(exit);
+
+                E::train::<B>(artifact_dir, config, device, Some(checkpoint));
+            }
+            Command::Infer => E::infer::<B::InnerBackend>(&run_dir(), device, cli.index),
+            Command::Evaluate => {
+                let artifact_dir = &run_dir();
+                let evaluation =
+                    E::evaluate::<B::InnerBackend>(artifact_dir, device).unwrap_or_else(exit);
+
+                println!("{evaluation}");
+                evaluation
+                    .save(artifact_dir)
+                    .expect("Evaluation should be saved successfully");
+            }
+            Command::Sweep => {
+                let path = cli.sweep.as_ref().unwrap_or_else(|| {
+                    exit(CliError::MissingValue {
+                        flag: "--sweep".to_string(),
+                    })
+                });
+                let sweep = SweepConfig::load_file(path).unwrap_or_else(exit);
+                let config = cli.apply(E::config()).unwrap_or_else(exit);
+
+                let report = run_sweep(&sweep, &config, runs_dir, |run_dir, config| {
+                    E::train::<B>(run_dir, config, device.clone(), None)
+                })
+                .unwrap_or_else(exit);
+                print!("{report}");
+            }
+            Command::Report => unreachable!("Reports are written without a backend"),
+        }
+    }
+}
+
+// Generic rather than `-> !` to be usable with `unwrap_or_else`
+fn exit<T>(err: impl Display) -> T {
+    eprintln!("{err}");
+    std::process::exit(1);
+}
@@ -0,0 +1,54 @@
+use burn::{
+    data::dataloader::batcher::Batcher,
+    tensor::{backend::Backend, Data, Int, Shape, Tensor},
+};
+
+use super::data::CsvItem;
+use crate::moons_data::batcher::MoonsBatch;
+use crate::toy_data::data::ToyBatch;
+
+/// Batches CSV rows into the `x`/`y` tensors of the moons classifier or of the toy
+/// regressor, depending on the batch type the data loader expects.
+#[derive(Clone)]
+pub struct CsvBatcher<B: Backend> {
+    device: B::Device,
+}
+
+impl<B: Backend> CsvBatcher<B> {
+    pub fn new(device: B::Device) -> Self {
+        Self { device }
+    }
+
+    // Features of shape `[batch_size, num_features]`
+    fn features(&self, items: &[CsvItem]) -> Tensor<B, 2> {
+        let num_features = items.first().map_or(0, |item| item.x.len());
+        let x = items.iter().flat_map(|item| item.x.clone()).collect();
+        let data = Data::new(x, Shape::new([items.len(), num_features]));
+
+        Tensor::<B, 2>::from_data(data.convert(), &self.device)
+    }
+}
+
+impl<B: Backend> Batcher<CsvItem, MoonsBatch<B>> for CsvBatcher<B> {
+    fn batch(&self, items: Vec<CsvItem>) -> MoonsBatch<B> {
+        let y = items.iter().map(|item| item.y as i64).collect();
+        let y = Data::new(y, Shape::new([items.len()]));
+
+        MoonsBatch {
+            x: self.features(&items),
+            y: Tensor::<B, 1, Int>::from_data(y.convert(), &self.device),
+        }
+    }
+}
+
+impl<B: Backend> Batcher<CsvItem, ToyBatch<B>> for CsvBatcher<B> {
+    fn batch(&self, items: Vec<CsvItem>) -> ToyBatch<B> {
+        let y = items.iter().map(|item| item.y).collect();
+        let y = Data::new(y, Shape::new([items.len(), 1]));
+
+        ToyBatch {
+            x: self.features(&items),
+            y: Tensor::<B, 2>::from_data(y.convert(), &self.device),
+        }
+    }
+}
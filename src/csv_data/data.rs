@@ -0,0 +1,366 @@
+use std::collections::BTreeSet;
+use std::fmt::Display;
+use std::path::PathBuf;
+
+use burn::config::{Config, ConfigError};
+use burn::data::dataset::Dataset;
+
+use crate::split::{SplitConfig, Splits};
+
+/// Cells considered missing, besides empty ones.
+const MISSING: [&str; 4] = ["na", "nan", "null", "?"];
+
+const PREPROCESSING_FILE: &str = "preprocessing.json";
+
+/// How the target column is turned into `y`.
+#[derive(Config, Debug, Copy, PartialEq, Eq)]
+pub enum TargetKind {
+    /// The target is a number.
+    Regression,
+    /// The target is a class name, replaced by its index among the sorted classes.
+    Classification,
+}
+
+/// What to do with the rows that miss a feature value. Rows without a target are
+/// always dropped.
+#[derive(Config, Debug, Copy, PartialEq)]
+pub enum MissingValues {
+    Drop,
+    /// Replaces the value by the mean of the feature over the training split.
+    Mean,
+    Fill {
+        value: f32,
+    },
+}
+
+#[derive(Debug)]
+pub enum CsvError {
+    Csv {
+        path: PathBuf,
+        source: csv::Error,
+    },
+    MissingColumn {
+        path: PathBuf,
+        column: String,
+    },
+    InvalidValue {
+        path: PathBuf,
+        line: u64,
+        column: String,
+        value: String,
+    },
+}
+
+impl Display for CsvError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CsvError::Csv { path, source } => {
+                write!(f, "Could not read {}: {source}", path.display())
+            }
+            CsvError::MissingColumn { path, column } => {
+                write!(f, "{} has no column {column}", path.display())
+            }
+            CsvError::InvalidValue {
+                path,
+                line,
+                column,
+                value,
+            } => write!(
+                f,
+                "Invalid value {value:?} for column {column} at line {line} of {}",
+                path.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CsvError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CsvError::Csv { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Row of a CSV file once preprocessed. `y` is the class index in classification.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CsvItem {
+    pub x: Vec<f32>,
+    pub y: f32,
+}
+
+pub struct CsvDataset {
+    dataset: Vec<CsvItem>,
+}
+
+impl Dataset<CsvItem> for CsvDataset {
+    fn get(&self, index: usize) -> Option<CsvItem> {
+        self.dataset.get(index).cloned()
+    }
+
+    fn len(&self) -> usize {
+        self.dataset.len()
+    }
+}
+
+/// Columns of a CSV file with a header row used as features and target.
+#[derive(Config, Debug)]
+pub struct CsvConfig {
+    pub path: String,
+    pub features: Vec<String>,
+    pub target: String,
+    #[config(default = "TargetKind::Regression")]
+    pub kind: TargetKind,
+    #[config(default = "MissingValues::Drop")]
+    pub missing: MissingValues,
+    /// Standardizes each feature with the statistics of the training split.
+    #[config(default = true)]
+    pub standardize: bool,
+}
+
+/// Everything needed to preprocess new rows the way the training data was, saved
+/// next to the model.
+#[derive(Config, Debug)]
+pub struct CsvPreprocessing {
+    pub features: Vec<String>,
+    /// Mean and standard deviation of each feature over the training split, before
+    /// the missing values are replaced.
+    pub mean: Vec<f32>,
+    pub std: Vec<f32>,
+    pub missing: MissingValues,
+    pub standardize: bool,
+    /// Class names indexed by label, empty in regression.
+    pub classes: Vec<String>,
+}
+
+/// Splits of a CSV file with the preprocessing fitted on its training split.
+pub struct CsvSplits {
+    pub train: CsvDataset,
+    pub valid: CsvDataset,
+    pub test: CsvDataset,
+    pub preprocessing: CsvPreprocessing,
+}
+
+// Row as read from the file, missing features are NaN
+struct Row {
+    x: Vec<f32>,
+    target: String,
+    line: u64,
+}
+
+impl CsvConfig {
+    /// Reads the file, splits its rows, then fits the preprocessing on the training
+    /// split and applies it to every split. Classification rows are split stratified
+    /// by class when `split.stratify` is set.
+    pub fn load(&self, split: &SplitConfig) -> Result<CsvSplits, CsvError> {
+        let (rows, classes) = self.read()?;
+
+        let rows = match self.missing {
+            MissingValues::Drop => rows
+                .into_iter()
+                .filter(|row| row.x.iter().all(|value| !value.is_nan()))
+                .collect(),
+            _ => rows,
+        };
+
+        let rows: Splits<Row> = split.split_by(rows, |row| match self.kind {
+            TargetKind::Classification => row.target.clone(),
+            TargetKind::Regression => String::new(),
+        });
+
+        let (mean, std) = statistics(&rows.train, self.features.len());
+        let preprocessing = CsvPreprocessing {
+            features: self.features.clone(),
+            mean,
+            std,
+            missing: self.missing,
+            standardize: self.standardize,
+            classes,
+        };
+
+        let dataset = |rows: Vec<Row>| -> Result<CsvDataset, CsvError> {
+            let dataset = rows
+                .into_iter()
+                .map(|row| {
+                    let y = self.target_value(&preprocessing, &row)?;
+                    Ok(CsvItem {
+                        x: preprocessing.apply(row.x),
+                        y,
+                    })
+                })
+                .collect::<Result<_, _>>()?;
+            Ok(CsvDataset { dataset })
+        };
+
+        Ok(CsvSplits {
+            train: dataset(rows.train)?,
+            valid: dataset(rows.valid)?,
+            test: dataset(rows.test)?,
+            preprocessing,
+        })
+    }
+
+    // Reads the feature values and the raw target of each row with a target, along
+    // with the sorted classes in classification
+    fn read(&self) -> Result<(Vec<Row>, Vec<String>), CsvError> {
+        let path = PathBuf::from(&self.path);
+        let csv_error = |source| CsvError::Csv {
+            path: path.clone(),
+            source,
+        };
+
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_path(&path)
+            .map_err(csv_error)?;
+        let headers = reader.headers().map_err(csv_error)?.clone();
+        let column = |name: &String| {
+            headers
+                .iter()
+                .position(|header| header == name)
+                .ok_or_else(|| CsvError::MissingColumn {
+                    path: path.clone(),
+                    column: name.clone(),
+                })
+        };
+        let features = self
+            .features
+            .iter()
+            .map(column)
+            .collect::<Result<Vec<_>, _>>()?;
+        let target_column = column(&self.target)?;
+
+        let mut rows = Vec::new();
+        for record in reader.records() {
+            let record = record.map_err(csv_error)?;
+            let line = record.position().map_or(0, |position| position.line());
+
+            let target = record.get(target_column).unwrap_or_default();
+            if is_missing(target) {
+                continue;
+            }
+
+            let x = features
+                .iter()
+                .zip(&self.features)
+                .map(|(&i, name)| {
+                    let value = record.get(i).unwrap_or_default();
+                    match is_missing(value) {
+                        true => Ok(f32::NAN),
+                        false => value.parse().map_err(|_| CsvError::InvalidValue {
+                            path: path.clone(),
+                            line,
+                            column: name.clone(),
+                            value: value.to_string(),
+                        }),
+                    }
+                })
+                .collect::<Result<_, _>>()?;
+
+            rows.push(Row {
+                x,
+                target: target.to_string(),
+                line,
+            });
+        }
+
+        let classes = match self.kind {
+            TargetKind::Classification => rows
+                .iter()
+                .map(|row| row.target.clone())
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect(),
+            TargetKind::Regression => Vec::new(),
+        };
+
+        Ok((rows, classes))
+    }
+
+    fn target_value(&self, preprocessing: &CsvPreprocessing, row: &Row) -> Result<f32, CsvError> {
+        let value = match self.kind {
+            TargetKind::Classification => {
+                preprocessing.label(&row.target).map(|label| label as f32)
+            }
+            TargetKind::Regression => row.target.parse().ok(),
+        };
+
+        value.ok_or_else(|| CsvError::InvalidValue {
+            path: PathBuf::from(&self.path),
+            line: row.line,
+            column: self.target.clone(),
+            value: row.target.clone(),
+        })
+    }
+}
+
+impl CsvPreprocessing {
+    /// Replaces the missing values, NaN, of a row of raw features then standardizes it.
+    pub fn apply(&self, x: Vec<f32>) -> Vec<f32> {
+        x.into_iter()
+            .enumerate()
+            .map(|(i, value)| {
+                let value = match (value.is_nan(), self.missing) {
+                    (false, _) => value,
+                    (true, MissingValues::Fill { value }) => value,
+                    (true, _) => self.mean[i],
+                };
+                match self.standardize {
+                    true => (value - self.mean[i]) / self.std[i],
+                    false => value,
+                }
+            })
+            .collect()
+    }
+
+    /// Index of a class name in classification.
+    pub fn label(&self, class: &str) -> Option<usize> {
+        self.classes
+            .binary_search_by(|name| name.as_str().cmp(class))
+            .ok()
+    }
+
+    /// Saves the preprocessing as `preprocessing.json` in the artifact directory.
+    pub fn save_to(&self, artifact_dir: &str) -> std::io::Result<()> {
+        self.save(format!("{artifact_dir}/{PREPROCESSING_FILE}"))
+    }
+
+    pub fn load_from(artifact_dir: &str) -> Result<Self, ConfigError> {
+        Self::load(format!("{artifact_dir}/{PREPROCESSING_FILE}"))
+    }
+}
+
+fn is_missing(value: &str) -> bool {
+    value.is_empty() || MISSING.contains(&value.to_lowercase().as_str())
+}
+
+// Mean and standard deviation of each feature ignoring missing values. Constant or
+// empty features get a standard deviation of 1 so that standardizing keeps them finite
+fn statistics(rows: &[Row], num_features: usize) -> (Vec<f32>, Vec<f32>) {
+    (0..num_features)
+        .map(|i| {
+            let values: Vec<f64> = rows
+                .iter()
+                .map(|row| row.x[i] as f64)
+                .filter(|value| !value.is_nan())
+                .collect();
+            if values.is_empty() {
+                return (0.0, 1.0);
+            }
+
+            let n = values.len() as f64;
+            let mean = values.iter().sum::<f64>() / n;
+            let variance = values
+                .iter()
+                .map(|value| (value - mean).powi(2))
+                .sum::<f64>()
+                / n;
+            let std = match variance.sqrt() {
+                std if std > 0.0 => std,
+                _ => 1.0,
+            };
+            (mean as f32, std as f32)
+        })
+        .unzip()
+}
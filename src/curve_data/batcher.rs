@@ -0,0 +1,43 @@
+use burn::data::dataloader::batcher::Batcher;
+use burn::prelude::{Float, Tensor};
+use burn::tensor::backend::Backend;
+use burn::tensor::Data;
+
+use super::data::CurveItem;
+use crate::toy_data::data::ToyBatch;
+
+/// Batches samples into a [`ToyBatch`], which the MLP regressor trains on.
+#[derive(Clone)]
+pub struct CurveBatcher<B: Backend> {
+    device: B::Device,
+}
+
+impl<B: Backend> CurveBatcher<B> {
+    pub fn new(device: B::Device) -> Self {
+        Self { device }
+    }
+}
+
+impl<B: Backend> Batcher<CurveItem, ToyBatch<B>> for CurveBatcher<B> {
+    fn batch(&self, items: Vec<CurveItem>) -> ToyBatch<B> {
+        let x = items
+            .iter()
+            .map(|item| Data::<f32, 1>::from([item.x]))
+            .map(|data| Tensor::<B, 1>::from_data(data.convert(), &self.device))
+            .map(|tensor| tensor.reshape([1, 1]))
+            .collect();
+
+        let y = items
+            .iter()
+            .map(|item| item.y)
+            .map(|y| Data::<f32, 1>::from([y]))
+            .map(|data| Tensor::<B, 1, Float>::from_data(data.convert(), &self.device))
+            .map(|tensor: Tensor<B, 1, Float>| tensor.reshape([1, 1]))
+            .collect();
+
+        let x = Tensor::cat(x, 0).to_device(&self.device);
+        let y = Tensor::cat(y, 0).to_device(&self.device);
+
+        ToyBatch { x, y }
+    }
+}
@@ -0,0 +1,118 @@
+use burn::config::Config;
+use burn::data::dataset::Dataset;
+use ndarray::{Array, Ix1};
+use ndarray_rand::rand::{self, SeedableRng};
+use ndarray_rand::rand_distr::{Distribution, Normal};
+
+use crate::split::{Split, SplitConfig};
+
+#[derive(Config, Debug)]
+pub enum CurveFunction {
+    /// `c0 + c1 * x + c2 * x² + ...`
+    Polynomial { coefficients: Vec<f64> },
+    /// `amplitude * sin(frequency * x + phase)`
+    Sinusoid {
+        amplitude: f64,
+        frequency: f64,
+        phase: f64,
+    },
+}
+
+impl CurveFunction {
+    pub fn eval(&self, x: f64) -> f64 {
+        match self {
+            CurveFunction::Polynomial { coefficients } => coefficients
+                .iter()
+                .rev()
+                .fold(0.0, |acc, coefficient| acc * x + coefficient),
+            CurveFunction::Sinusoid {
+                amplitude,
+                frequency,
+                phase,
+            } => amplitude * (frequency * x + phase).sin(),
+        }
+    }
+}
+
+/// One dimensional regression data `y = f(x) + noise` on evenly spaced inputs. The
+/// noise is heteroscedastic: its standard deviation is `noise_std + noise_slope * |x|`.
+#[derive(Config, Debug)]
+pub struct CurveConfig {
+    pub function: CurveFunction,
+    pub start: f32,
+    pub end: f32,
+    pub n: usize,
+    #[config(default = 0.1)]
+    pub noise_std: f64,
+    #[config(default = 0.0)]
+    pub noise_slope: f64,
+    #[config(default = 21)]
+    pub seed: u64,
+}
+
+impl CurveConfig {
+    pub fn generate(&self) -> Vec<CurveItem> {
+        let x = Array::<f32, Ix1>::linspace(self.start, self.end, self.n);
+
+        let normal = Normal::<f64>::new(0.0, 1.0).unwrap();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(self.seed);
+        let y = x.mapv(|x| {
+            let x = x as f64;
+            let std = self.noise_std + self.noise_slope * x.abs();
+            (self.function.eval(x) + std * normal.sample(&mut rng)) as f32
+        });
+
+        x.into_iter()
+            .zip(y)
+            .map(|(x, y)| CurveItem { x, y })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CurveItem {
+    pub x: f32,
+    pub y: f32,
+}
+
+pub struct CurveDataset {
+    dataset: Vec<CurveItem>,
+}
+
+impl Dataset<CurveItem> for CurveDataset {
+    fn get(&self, index: usize) -> Option<CurveItem> {
+        self.dataset.get(index).cloned()
+    }
+
+    fn len(&self) -> usize {
+        self.dataset.len()
+    }
+}
+
+pub struct CurveDatasetConfig {
+    pub curve: CurveConfig,
+    pub split: SplitConfig,
+}
+
+impl CurveDatasetConfig {
+    pub fn train(&self) -> CurveDataset {
+        self.dataset(Split::Train)
+    }
+
+    pub fn valid(&self) -> CurveDataset {
+        self.dataset(Split::Valid)
+    }
+
+    pub fn test(&self) -> CurveDataset {
+        self.dataset(Split::Test)
+    }
+
+    fn dataset(&self, split: Split) -> CurveDataset {
+        let items = self.curve.generate();
+        let splits = self.split.split(items);
+
+        CurveDataset {
+            dataset: splits.take(split),
+        }
+    }
+}
@@ -0,0 +1,214 @@
+use std::collections::BTreeSet;
+
+use burn::{
+    config::Config,
+    train::{
+        checkpoint::{CheckpointingAction, CheckpointingStrategy},
+        metric::store::{Aggregate, EventStoreClient, Split},
+        EarlyStoppingStrategy,
+    },
+};
+
+use crate::history::EpochValue;
+
+/// Whether a metric improves by going down, like a loss, or up, like an accuracy.
+#[derive(Config, Debug, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Min,
+    Max,
+}
+
+/// Validation metric used to pick the best epoch, whose checkpoint becomes the saved
+/// model, and to stop the training once it no longer improves.
+#[derive(Config, Debug)]
+pub struct EarlyStoppingConfig {
+    /// Name of the metric as logged by the learner, e.g. `Loss` or `Accuracy`.
+    #[config(default = "String::from(\"Loss\")")]
+    pub metric: String,
+    /// Derived from the metric when unset, `Max` for `Accuracy` and `Min` for `Loss`.
+    pub mode: Option<Mode>,
+    /// Epochs without improvement before stopping, the training runs every epoch when
+    /// unset.
+    pub patience: Option<usize>,
+    /// Smallest change of the metric counted as an improvement.
+    #[config(default = 0.0)]
+    pub min_delta: f64,
+}
+
+impl EarlyStoppingConfig {
+    /// Direction in which the metric improves, `Min` for metrics other than the
+    /// accuracy and the loss unless set.
+    pub fn mode(&self) -> Mode {
+        self.mode
+            .or_else(|| metric_mode(&self.metric))
+            .unwrap_or(Mode::Min)
+    }
+
+    /// Whether `value` improves on the `best` value so far by more than `min_delta`.
+    pub fn improves(&self, value: f64, best: Option<f64>) -> bool {
+        match (best, self.mode()) {
+            (None, _) => !value.is_nan(),
+            (Some(best), Mode::Min) => value < best - self.min_delta,
+            (Some(best), Mode::Max) => value > best + self.min_delta,
+        }
+    }
+
+    /// Best of the values of the metric, the first one when later ones don't improve
+    /// on it by more than `min_delta`.
+    pub fn best(&self, values: &[EpochValue]) -> Option<EpochValue> {
+        values.iter().fold(None, |best, value| {
+            match self.improves(value.value, best.as_ref().map(|best| best.value)) {
+                true => Some(value.clone()),
+                false => best,
+            }
+        })
+    }
+
+    /// Checks that the metric is one of the validation `metrics` registered by the
+    /// learner, otherwise it would never be found, and that the mode does not pick
+    /// its worst epoch.
+    pub fn check_metric(&self, metrics: &[&str]) {
+        assert!(
+            metrics.contains(&self.metric.as_str()),
+            "Unknown early stopping metric {}, expected one of {}",
+            self.metric,
+            metrics.join(", ")
+        );
+        if let (Some(mode), Some(expected)) = (self.mode, metric_mode(&self.metric)) {
+            assert!(
+                mode == expected,
+                "Early stopping metric {} improves with mode {expected:?}, not {mode:?}",
+                self.metric
+            );
+        }
+    }
+
+    /// Returns the strategy stopping the training, `None` without patience.
+    pub fn early_stopping(&self) -> Option<EarlyStopping> {
+        self.patience.map(|patience| EarlyStopping {
+            config: self.clone(),
+            patience,
+            best: None,
+        })
+    }
+
+    /// Returns the strategy keeping the checkpoints of the best and the last epochs.
+    pub fn checkpointing(&self) -> BestCheckpointing {
+        BestCheckpointing {
+            config: self.clone(),
+            best: None,
+            last: None,
+        }
+    }
+
+    fn find(&self, epoch: usize, store: &EventStoreClient) -> Option<f64> {
+        store.find_metric(&self.metric, epoch, Aggregate::Mean, Split::Valid)
+    }
+}
+
+pub struct EarlyStopping {
+    config: EarlyStoppingConfig,
+    patience: usize,
+    best: Option<EpochValue>,
+}
+
+impl EarlyStopping {
+    /// Starts from the best value of the epochs before a checkpoint, since the learner
+    /// only restores the model, optimizer and scheduler when resuming.
+    pub fn with_best(mut self, best: Option<EpochValue>) -> Self {
+        self.best = best;
+        self
+    }
+
+    /// Whether to stop after `epoch`, given its validation value of the metric. Never
+    /// stops before the metric was recorded once.
+    pub fn update(&mut self, epoch: usize, value: Option<f64>) -> bool {
+        if let Some(value) = value {
+            if self
+                .config
+                .improves(value, self.best.as_ref().map(|b| b.value))
+            {
+                self.best = Some(EpochValue { epoch, value });
+                return false;
+            }
+        }
+
+        self.best
+            .as_ref()
+            .is_some_and(|best| epoch - best.epoch >= self.patience)
+    }
+}
+
+impl EarlyStoppingStrategy for EarlyStopping {
+    fn should_stop(&mut self, epoch: usize, store: &EventStoreClient) -> bool {
+        let value = self.config.find(epoch, store);
+        self.update(epoch, value)
+    }
+}
+
+/// Keeps the checkpoint of the best epoch, and the one of the last epoch to be able
+/// to resume the training.
+pub struct BestCheckpointing {
+    config: EarlyStoppingConfig,
+    best: Option<EpochValue>,
+    last: Option<usize>,
+}
+
+impl BestCheckpointing {
+    /// Starts from the best value of the epochs before the `checkpoint` epoch, so
+    /// that the checkpoints saved before resuming are still tracked.
+    pub fn resumed(mut self, best: Option<EpochValue>, checkpoint: usize) -> Self {
+        self.best = best;
+        self.last = Some(checkpoint);
+        self
+    }
+
+    /// Saves the checkpoint of `epoch`, given its validation value of the metric, and
+    /// deletes the ones that are neither the best nor the last anymore.
+    pub fn update(&mut self, epoch: usize, value: Option<f64>) -> Vec<CheckpointingAction> {
+        let previous_best = self.best.as_ref().map(|best| best.epoch);
+
+        if let Some(value) = value {
+            if self
+                .config
+                .improves(value, self.best.as_ref().map(|b| b.value))
+            {
+                self.best = Some(EpochValue { epoch, value });
+            }
+        }
+
+        let best = self.best.as_ref().map(|best| best.epoch);
+        let outdated: BTreeSet<usize> = [previous_best, self.last]
+            .into_iter()
+            .flatten()
+            .filter(|&previous| Some(previous) != best)
+            .collect();
+        self.last = Some(epoch);
+
+        outdated
+            .into_iter()
+            .map(CheckpointingAction::Delete)
+            .chain([CheckpointingAction::Save])
+            .collect()
+    }
+}
+
+impl CheckpointingStrategy for BestCheckpointing {
+    fn checkpointing(
+        &mut self,
+        epoch: usize,
+        store: &EventStoreClient,
+    ) -> Vec<CheckpointingAction> {
+        let value = self.config.find(epoch, store);
+        self.update(epoch, value)
+    }
+}
+
+// Direction of the metrics registered by the learners
+fn metric_mode(metric: &str) -> Option<Mode> {
+    match metric {
+        "Accuracy" => Some(Mode::Max),
+        "Loss" => Some(Mode::Min),
+        _ => None,
+    }
+}
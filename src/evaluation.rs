@@ -0,0 +1,104 @@
+use std::fmt::Display;
+use std::sync::Arc;
+
+use burn::{
+    data::dataloader::DataLoader,
+    tensor::{backend::Backend, ElementConversion},
+    train::{ClassificationOutput, RegressionOutput, ValidStep},
+};
+use serde::Serialize;
+
+/// Metrics of a trained model averaged over a whole dataset.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Evaluation {
+    pub num_items: usize,
+    /// Average loss of the items, `None` when there are none.
+    pub loss: Option<f64>,
+    /// Percentage of correct predictions, only computed for classifiers with items.
+    pub accuracy: Option<f64>,
+}
+
+impl Display for Evaluation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Some(loss) = self.loss else {
+            return write!(f, "No items to evaluate");
+        };
+
+        write!(f, "Loss {loss:.4}")?;
+        if let Some(accuracy) = self.accuracy {
+            write!(f, ", Accuracy {accuracy:.2}%")?;
+        }
+        write!(f, " over {} items", self.num_items)
+    }
+}
+
+impl Evaluation {
+    /// Saves the evaluation as `evaluation.json` in the artifact directory.
+    pub fn save(&self, artifact_dir: &str) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(self).expect("Evaluation should be serialized");
+        std::fs::write(format!("{artifact_dir}/evaluation.json"), json)
+    }
+}
+
+/// Evaluates a classifier on every batch of the data loader.
+pub fn evaluate_classifier<B, M, I>(model: &M, dataloader: Arc<dyn DataLoader<I>>) -> Evaluation
+where
+    B: Backend,
+    M: ValidStep<I, ClassificationOutput<B>>,
+{
+    let mut num_items = 0;
+    let mut loss = 0.0;
+    let mut correct = 0.0;
+
+    for batch in dataloader.iter() {
+        let output = model.step(batch);
+        let [batch_size] = output.targets.dims();
+
+        loss += output.loss.into_scalar().elem::<f64>() * batch_size as f64;
+        correct += output
+            .output
+            .argmax(1)
+            .flatten::<1>(0, 1)
+            .equal(output.targets)
+            .int()
+            .sum()
+            .into_scalar()
+            .elem::<f64>();
+        num_items += batch_size;
+    }
+
+    Evaluation {
+        num_items,
+        loss: mean(loss, num_items),
+        accuracy: mean(100.0 * correct, num_items),
+    }
+}
+
+/// Evaluates a regression model on every batch of the data loader.
+pub fn evaluate_regressor<B, M, I>(model: &M, dataloader: Arc<dyn DataLoader<I>>) -> Evaluation
+where
+    B: Backend,
+    M: ValidStep<I, RegressionOutput<B>>,
+{
+    let mut num_items = 0;
+    let mut loss = 0.0;
+
+    for batch in dataloader.iter() {
+        let output = model.step(batch);
+        let [batch_size, _] = output.targets.dims();
+
+        loss += output.loss.into_scalar().elem::<f64>() * batch_size as f64;
+        num_items += batch_size;
+    }
+
+    Evaluation {
+        num_items,
+        loss: mean(loss, num_items),
+        accuracy: None,
+    }
+}
+
+// Average of the items, undefined for an empty data loader
+fn mean(total: f64, num_items: usize) -> Option<f64> {
+    (num_items > 0).then(|| total / num_items as f64)
+}
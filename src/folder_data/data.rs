@@ -0,0 +1,207 @@
+use std::fmt::Display;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use burn::config::Config;
+use burn::data::dataset::Dataset;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageError};
+
+use crate::split::Split;
+use crate::transform::Image;
+
+const EXTENSIONS: [&str; 3] = ["png", "jpg", "jpeg"];
+
+#[derive(Debug)]
+pub enum FolderError {
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    NoClasses {
+        root: PathBuf,
+    },
+    Decode {
+        path: PathBuf,
+        source: ImageError,
+    },
+}
+
+impl Display for FolderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FolderError::Io { path, source } => {
+                write!(f, "Could not read {}: {source}", path.display())
+            }
+            FolderError::NoClasses { root } => write!(
+                f,
+                "{} has no class directory, expected <root>/<class_name>/<image>",
+                root.display()
+            ),
+            FolderError::Decode { path, source } => {
+                write!(f, "Could not decode {}: {source}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for FolderError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FolderError::Io { source, .. } => Some(source),
+            FolderError::Decode { source, .. } => Some(source),
+            FolderError::NoClasses { .. } => None,
+        }
+    }
+}
+
+/// Image decoded from `path` with pixels in `[0, 255]`, labelled by its directory.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImageFolderItem {
+    pub image: Image,
+    pub label: usize,
+    pub path: PathBuf,
+}
+
+/// Labelled images stored as `root/<class_name>/<image>.{png,jpg,jpeg}`.
+#[derive(Config, Debug)]
+pub struct ImageFolderConfig {
+    pub root: String,
+    /// Every image is resized to `height` x `width`.
+    pub height: usize,
+    pub width: usize,
+    /// Images are converted to grayscale (1), RGB (3) or RGBA (4).
+    #[config(default = 1)]
+    pub channels: usize,
+}
+
+impl ImageFolderConfig {
+    /// Reads the images of the class directories in `root`.
+    pub fn load(&self) -> Result<ImageFolderDataset, FolderError> {
+        ImageFolderDataset::new(&self.root, self)
+    }
+
+    /// Reads the images of `root/{train,valid,test}`, e.g.
+    /// `root/train/<class_name>/<image>`.
+    pub fn split(&self, split: Split) -> Result<ImageFolderDataset, FolderError> {
+        ImageFolderDataset::new(Path::new(&self.root).join(split.name()), self)
+    }
+
+    /// Sorted names of the class directories of the split, without decoding their
+    /// images.
+    pub fn classes(&self, split: Split) -> Result<Vec<String>, FolderError> {
+        classes(&Path::new(&self.root).join(split.name()))
+    }
+}
+
+/// Images decoded from the class directories of a root directory. Classes are sorted
+/// by name, so a label is the index of its class directory in alphabetical order and
+/// stays the same between the splits as long as they have the same classes.
+pub struct ImageFolderDataset {
+    dataset: Vec<ImageFolderItem>,
+    classes: Vec<String>,
+}
+
+impl Dataset<ImageFolderItem> for ImageFolderDataset {
+    fn get(&self, index: usize) -> Option<ImageFolderItem> {
+        self.dataset.get(index).cloned()
+    }
+
+    fn len(&self) -> usize {
+        self.dataset.len()
+    }
+}
+
+impl ImageFolderDataset {
+    pub fn new(root: impl AsRef<Path>, config: &ImageFolderConfig) -> Result<Self, FolderError> {
+        assert!(
+            matches!(config.channels, 1 | 3 | 4),
+            "Images can only be converted to 1, 3 or 4 channels, got {}",
+            config.channels
+        );
+
+        let root = root.as_ref();
+        let classes = classes(root)?;
+
+        let mut dataset = Vec::new();
+        for (label, class) in classes.iter().enumerate() {
+            for path in sorted_entries(&root.join(class))? {
+                if !is_image(&path) {
+                    continue;
+                }
+
+                let image = image::open(&path).map_err(|source| FolderError::Decode {
+                    path: path.clone(),
+                    source,
+                })?;
+                dataset.push(ImageFolderItem {
+                    image: convert(image, config),
+                    label,
+                    path,
+                });
+            }
+        }
+
+        Ok(Self { dataset, classes })
+    }
+
+    /// Class names indexed by label.
+    pub fn classes(&self) -> &[String] {
+        &self.classes
+    }
+}
+
+fn classes(root: &Path) -> Result<Vec<String>, FolderError> {
+    let classes: Vec<String> = sorted_entries(root)?
+        .into_iter()
+        .filter(|path| path.is_dir())
+        .filter_map(|path| Some(path.file_name()?.to_str()?.to_string()))
+        .collect();
+    if classes.is_empty() {
+        return Err(FolderError::NoClasses {
+            root: root.to_path_buf(),
+        });
+    }
+    Ok(classes)
+}
+
+fn sorted_entries(dir: &Path) -> Result<Vec<PathBuf>, FolderError> {
+    let io_error = |source| FolderError::Io {
+        path: dir.to_path_buf(),
+        source,
+    };
+
+    let mut paths = fs::read_dir(dir)
+        .map_err(io_error)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(io_error)?;
+    paths.sort();
+    Ok(paths)
+}
+
+fn is_image(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| EXTENSIONS.contains(&extension.to_lowercase().as_str()))
+}
+
+// Resizes and converts the decoded image, then stores its pixels channel first
+fn convert(image: DynamicImage, config: &ImageFolderConfig) -> Image {
+    let (height, width) = (config.height, config.width);
+    let image = image.resize_exact(width as u32, height as u32, FilterType::Triangle);
+    let pixels = match config.channels {
+        1 => image.to_luma8().into_raw(),
+        3 => image.to_rgb8().into_raw(),
+        _ => image.to_rgba8().into_raw(),
+    };
+
+    let mut output = Image::filled(config.channels, height, width, 0.0);
+    for (i, pixel) in pixels.into_iter().enumerate() {
+        let (c, position) = (i % config.channels, i / config.channels);
+        output.set(c, position / width, position % width, pixel as f32);
+    }
+    output
+}
@@ -0,0 +1,251 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+
+use plotly::Plot;
+use serde::Serialize;
+
+use crate::report;
+
+/// Splits logged by the learner, each in its own directory of the artifact directory.
+pub const SPLITS: [&str; 2] = ["train", "valid"];
+
+const EPOCH_PREFIX: &str = "epoch-";
+
+#[derive(Debug)]
+pub enum HistoryError {
+    MissingLogs {
+        dir: PathBuf,
+    },
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    InvalidEntry {
+        path: PathBuf,
+        line: usize,
+        entry: String,
+    },
+}
+
+impl Display for HistoryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HistoryError::MissingLogs { dir } => {
+                write!(f, "No metric logs were found in {}", dir.display())
+            }
+            HistoryError::Io { path, source } => {
+                write!(f, "Could not read {}: {source}", path.display())
+            }
+            HistoryError::InvalidEntry { path, line, entry } => write!(
+                f,
+                "Invalid metric entry {entry:?} on line {line} of {}",
+                path.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for HistoryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            HistoryError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Value of a metric averaged over one epoch.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct EpochValue {
+    pub epoch: usize,
+    pub value: f64,
+}
+
+/// Per-epoch values of one metric on one split.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct MetricHistory {
+    pub split: String,
+    pub metric: String,
+    pub epochs: Vec<EpochValue>,
+}
+
+/// Metrics logged by the learner in `{artifact_dir}/{train,valid}/epoch-{n}/{Metric}.log`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct TrainingHistory {
+    pub metrics: Vec<MetricHistory>,
+}
+
+impl TrainingHistory {
+    /// Reads the metric logs of every split found in the artifact directory.
+    pub fn load(artifact_dir: impl AsRef<Path>) -> Result<Self, HistoryError> {
+        let artifact_dir = artifact_dir.as_ref();
+        let mut metrics = Vec::new();
+
+        for split in SPLITS {
+            let dir = artifact_dir.join(split);
+            if dir.is_dir() {
+                metrics.extend(read_split(&dir, split)?);
+            }
+        }
+
+        if metrics.is_empty() {
+            return Err(HistoryError::MissingLogs {
+                dir: artifact_dir.to_path_buf(),
+            });
+        }
+
+        Ok(Self { metrics })
+    }
+
+    /// Returns the history of `metric` on `split`, e.g. `("valid", "Loss")`.
+    pub fn get(&self, split: &str, metric: &str) -> Option<&MetricHistory> {
+        self.metrics
+            .iter()
+            .find(|history| history.split == split && history.metric == metric)
+    }
+
+    /// Names of the logged metrics, in alphabetical order.
+    pub fn metric_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.metrics.iter().map(|h| h.metric.as_str()).collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    /// Learning curves with one subplot per metric and one trace per split.
+    pub fn plot(&self) -> Plot {
+        report::curves(
+            self.metrics
+                .iter()
+                .map(|history| (format!("{} ({})", history.metric, history.split), history)),
+        )
+    }
+
+    /// One `split,metric,epoch,value` row per epoch value, with a header.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("split,metric,epoch,value\n");
+        for history in &self.metrics {
+            for value in &history.epochs {
+                csv.push_str(&format!(
+                    "{},{},{},{}\n",
+                    history.split, history.metric, value.epoch, value.value
+                ));
+            }
+        }
+        csv
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("History should be serialized successfully")
+    }
+
+    /// Writes `history.html`, `history.csv` and `history.json` in `dir`.
+    pub fn write(&self, dir: impl AsRef<Path>) -> Result<(), HistoryError> {
+        let dir = dir.as_ref();
+
+        let mut plot = self.plot();
+        plot.use_local_plotly();
+        plot.write_html(dir.join("history.html"));
+
+        for (name, contents) in [
+            ("history.csv", self.to_csv()),
+            ("history.json", self.to_json()),
+        ] {
+            let path = dir.join(name);
+            std::fs::write(&path, contents).map_err(|source| HistoryError::Io { path, source })?;
+        }
+
+        Ok(())
+    }
+}
+
+fn read_split(dir: &Path, split: &str) -> Result<Vec<MetricHistory>, HistoryError> {
+    let mut metrics: BTreeMap<String, Vec<EpochValue>> = BTreeMap::new();
+
+    let epoch_dirs = read_dir(dir)?.into_iter().filter_map(|path| {
+        let epoch = path.file_name()?.to_str()?.strip_prefix(EPOCH_PREFIX)?;
+        Some((epoch.parse::<usize>().ok()?, path))
+    });
+
+    for (epoch, epoch_dir) in epoch_dirs {
+        for path in read_dir(&epoch_dir)? {
+            let metric = match path.extension() {
+                Some(extension) if extension == "log" => path.file_stem(),
+                _ => None,
+            };
+
+            if let Some(metric) = metric.and_then(|metric| metric.to_str()) {
+                if let Some(value) = read_log(&path)? {
+                    metrics
+                        .entry(metric.replace('_', " "))
+                        .or_default()
+                        .push(EpochValue { epoch, value });
+                }
+            }
+        }
+    }
+
+    Ok(metrics
+        .into_iter()
+        .map(|(metric, mut epochs)| {
+            epochs.sort_by_key(|value| value.epoch);
+            MetricHistory {
+                split: split.to_string(),
+                metric,
+                epochs,
+            }
+        })
+        .collect())
+}
+
+fn read_dir(dir: &Path) -> Result<Vec<PathBuf>, HistoryError> {
+    let io_error = |source| HistoryError::Io {
+        path: dir.to_path_buf(),
+        source,
+    };
+
+    std::fs::read_dir(dir)
+        .map_err(io_error)?
+        .map(|entry| entry.map(|entry| entry.path()).map_err(io_error))
+        .collect()
+}
+
+// Each line is either a `value` or an aggregated `value,count` of one iteration, the
+// epoch value is their mean weighted by the counts like the learner reports it
+fn read_log(path: &Path) -> Result<Option<f64>, HistoryError> {
+    let contents = std::fs::read_to_string(path).map_err(|source| HistoryError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    let mut sum = 0.0;
+    let mut count = 0;
+
+    for (line, entry) in contents.lines().enumerate() {
+        if entry.is_empty() {
+            continue;
+        }
+
+        let invalid = || HistoryError::InvalidEntry {
+            path: path.to_path_buf(),
+            line: line + 1,
+            entry: entry.to_string(),
+        };
+
+        let (value, n) = match entry.split_once(',') {
+            Some((value, n)) => (value, n.parse::<usize>().map_err(|_| invalid())?),
+            None => (entry, 1),
+        };
+        let value = value.parse::<f64>().map_err(|_| invalid())?;
+
+        sum += value * n as f64;
+        count += n;
+    }
+
+    if count == 0 {
+        return Ok(None);
+    }
+
+    Ok(Some(sum / count as f64))
+}
@@ -0,0 +1,329 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use burn::{
+    config::Config,
+    data::{
+        dataloader::{batcher::Batcher, DataLoader},
+        dataset::{vision::MnistItem, Dataset},
+    },
+    tensor::{
+        backend::{AutodiffBackend, Backend},
+        ElementConversion,
+    },
+};
+
+use crate::augmentation::AugmentationConfig;
+use crate::cifar_data::binary::{self, CifarDataset};
+use crate::cifar_data::data as cifar;
+use crate::evaluation::{evaluate_classifier, Evaluation};
+use crate::folder_data::data::{ImageFolderConfig, ImageFolderDataset};
+use crate::inference::{load_artifact, LoadError};
+use crate::mist_data::data::{MnistBatch, MnistBatcher};
+use crate::mist_data::variants::{EmnistSplit, MnistVariant};
+use crate::model::cnn::CnnConfig;
+use crate::runs::data_files;
+use crate::split::Split;
+use crate::train::{train_classifier, ArtifactConfig, TrainingConfig};
+use crate::transform::TransformPipeline;
+
+/// Dataset of the chapter 3 experiments, one of the MNIST variants or CIFAR-10.
+#[derive(Config, Debug, Copy, PartialEq, Eq)]
+pub enum ImageDataset {
+    Mnist,
+    FashionMnist,
+    Kmnist,
+    Emnist {
+        split: EmnistSplit,
+    },
+    /// RGB images of 10 classes, read from the files of the binary version.
+    Cifar10,
+}
+
+impl From<MnistVariant> for ImageDataset {
+    fn from(variant: MnistVariant) -> Self {
+        match variant {
+            MnistVariant::Mnist => ImageDataset::Mnist,
+            MnistVariant::FashionMnist => ImageDataset::FashionMnist,
+            MnistVariant::Kmnist => ImageDataset::Kmnist,
+            MnistVariant::Emnist { split } => ImageDataset::Emnist { split },
+        }
+    }
+}
+
+impl ImageDataset {
+    /// MNIST variant of the dataset, `None` for CIFAR-10.
+    pub fn mnist(&self) -> Option<MnistVariant> {
+        match self {
+            ImageDataset::Mnist => Some(MnistVariant::Mnist),
+            ImageDataset::FashionMnist => Some(MnistVariant::FashionMnist),
+            ImageDataset::Kmnist => Some(MnistVariant::Kmnist),
+            ImageDataset::Emnist { split } => Some(MnistVariant::Emnist { split: *split }),
+            ImageDataset::Cifar10 => None,
+        }
+    }
+
+    /// Scales the pixels to `[0, 1]` then standardizes each channel with the
+    /// statistics of the dataset.
+    pub fn transform(&self) -> TransformPipeline {
+        match self.mnist() {
+            Some(variant) => variant.transform(),
+            None => cifar::transform(),
+        }
+    }
+
+    /// Shape `[channels, height, width]` of the images.
+    pub fn image_shape(&self) -> [usize; 3] {
+        match self.mnist() {
+            Some(_) => [1, 28, 28],
+            None => [binary::CHANNELS, binary::HEIGHT, binary::WIDTH],
+        }
+    }
+
+    /// Name of each class, indexed by label.
+    pub fn class_names(&self) -> Vec<&'static str> {
+        match self.mnist() {
+            Some(variant) => variant.class_names(),
+            None => cifar::CLASSES.to_vec(),
+        }
+    }
+
+    pub fn num_classes(&self) -> usize {
+        self.class_names().len()
+    }
+}
+
+/// Images the chapter 3 models are trained on, with their transforms and augmentations.
+#[derive(Config)]
+pub struct ImageDataConfig {
+    #[config(default = "ImageDataset::Mnist")]
+    pub dataset: ImageDataset,
+    /// Directory of the dataset IDX files, or of the CIFAR-10 binary files. MNIST is
+    /// downloaded when not given.
+    pub data_dir: Option<String>,
+    /// Transforms of each image, the normalization of the dataset when not given.
+    pub transform: Option<TransformPipeline>,
+    pub augmentation: Option<AugmentationConfig>,
+    /// Labelled images read from `root/train` and `root/test` instead of the dataset.
+    pub image_folder: Option<ImageFolderConfig>,
+}
+
+impl ImageDataConfig {
+    /// Batcher applying the configured transforms, shared by training and inference.
+    pub fn batcher<B: Backend>(&self, device: B::Device) -> MnistBatcher<B> {
+        let transform = match &self.transform {
+            Some(transform) => transform.clone(),
+            None => self.dataset.transform(),
+        };
+        transform
+            .output_shape(self.image_shape())
+            .unwrap_or_else(|err| panic!("The transforms do not fit the images: {err}"));
+
+        MnistBatcher::new(device).with_transform(transform)
+    }
+
+    /// Shape `[channels, height, width]` of the images before the transforms.
+    pub fn image_shape(&self) -> [usize; 3] {
+        match &self.image_folder {
+            Some(folder) => [folder.channels, folder.height, folder.width],
+            None => self.dataset.image_shape(),
+        }
+    }
+
+    /// Reads the training or test split of an MNIST variant.
+    pub fn mnist_dataset(
+        &self,
+        variant: MnistVariant,
+        split: Split,
+    ) -> Box<dyn Dataset<MnistItem>> {
+        variant
+            .dataset(self.data_dir.as_deref(), split)
+            .unwrap_or_else(|err| panic!("Could not read the {split} split: {err}"))
+    }
+
+    /// Reads the training or test split of CIFAR-10 from the data directory.
+    pub fn cifar_dataset(&self, split: Split) -> CifarDataset {
+        let Some(dir) = &self.data_dir else {
+            panic!("CIFAR-10 is read from the binary files of the data directory, none was given");
+        };
+
+        CifarDataset::split(dir, split)
+            .unwrap_or_else(|err| panic!("Could not read the {split} split: {err}"))
+    }
+
+    /// Reads the directory of the split in the image folder.
+    pub fn folder_dataset(&self, folder: &ImageFolderConfig, split: Split) -> ImageFolderDataset {
+        folder
+            .split(split)
+            .unwrap_or_else(|err| panic!("Could not read the {split} images: {err}"))
+    }
+
+    /// Data loader of the training or test split, from the image folder when given.
+    pub fn dataloader<B: Backend>(
+        &self,
+        training: &TrainingConfig,
+        batcher: MnistBatcher<B>,
+        split: Split,
+    ) -> Arc<dyn DataLoader<MnistBatch<B>>> {
+        match (&self.image_folder, self.dataset.mnist()) {
+            (Some(folder), _) => training.dataloader(batcher, self.folder_dataset(folder, split)),
+            (None, Some(variant)) => {
+                training.dataloader(batcher, self.mnist_dataset(variant, split))
+            }
+            (None, None) => training.dataloader(batcher, self.cifar_dataset(split)),
+        }
+    }
+
+    /// Same as [`ImageDataConfig::dataloader`] on the `train` split, with the
+    /// configured augmentations.
+    pub fn train_dataloader<B: Backend>(
+        &self,
+        training: &TrainingConfig,
+        batcher: MnistBatcher<B>,
+    ) -> Arc<dyn DataLoader<MnistBatch<B>>> {
+        let batcher = match &self.augmentation {
+            Some(augmentation) => batcher.with_augmenter(augmentation.init()),
+            None => batcher,
+        };
+
+        self.dataloader(training, batcher, Split::Train)
+    }
+
+    /// Classes of the configured dataset, or class directories of the image folder.
+    pub fn num_classes(&self) -> usize {
+        match &self.image_folder {
+            Some(folder) => folder
+                .classes(Split::Train)
+                .unwrap_or_else(|err| panic!("Could not read the train classes: {err}"))
+                .len(),
+            None => self.dataset.num_classes(),
+        }
+    }
+
+    /// Files of the image folder or of the data directory, downloaded MNIST has none.
+    pub fn files(&self) -> Vec<PathBuf> {
+        match (&self.image_folder, &self.data_dir) {
+            (Some(folder), _) => data_files(&folder.root),
+            (None, Some(dir)) => data_files(dir),
+            (None, None) => Vec::new(),
+        }
+    }
+
+    /// Description of the images for [`ArtifactConfig::dataset`].
+    pub fn description(&self) -> String {
+        match &self.image_folder {
+            Some(folder) => folder.to_string(),
+            None => format!("{} {:?}", self.dataset, self.data_dir),
+        }
+    }
+}
+
+/// Config of an image classification experiment, so that a chapter only has to
+/// supply its model and the rest is shared.
+pub trait ImageExperiment: ArtifactConfig {
+    fn data(&self) -> &ImageDataConfig;
+
+    /// Model classifying the images of [`ImageExperiment::data`] into its
+    /// [`ImageDataConfig::num_classes`].
+    fn cnn(&self) -> CnnConfig;
+}
+
+/// Trains the model on the training split, validated on the test split.
+pub fn train<B: AutodiffBackend, C: ImageExperiment>(
+    artifact_dir: &str,
+    config: &C,
+    device: B::Device,
+    checkpoint: Option<usize>,
+) {
+    let data = config.data();
+    let training = config.training();
+    let dataloader_train = data.train_dataloader(training, data.batcher::<B>(device.clone()));
+    let dataloader_valid = data.dataloader(
+        training,
+        data.batcher::<B::InnerBackend>(device.clone()),
+        Split::Test,
+    );
+
+    train_classifier(
+        artifact_dir,
+        config,
+        |device| config.cnn().init::<B>(device),
+        dataloader_train,
+        dataloader_valid,
+        device,
+        checkpoint,
+    );
+}
+
+/// Metrics of the trained model on the test split.
+pub fn evaluate<B: Backend, C: ImageExperiment>(
+    artifact_dir: &str,
+    device: B::Device,
+) -> Result<Evaluation, LoadError> {
+    let (config, model) = load_artifact::<B, C, _>(
+        artifact_dir,
+        |config, device| config.cnn().init(device),
+        &device,
+    )?;
+    let data = config.data();
+    let dataloader_test =
+        data.dataloader(config.training(), data.batcher::<B>(device), Split::Test);
+
+    Ok(evaluate_classifier(&model, dataloader_test))
+}
+
+/// Prints the predicted and expected class of the test item at `index`.
+pub fn infer<B: Backend, C: ImageExperiment>(artifact_dir: &str, device: B::Device, index: usize) {
+    let (config, model) = match load_artifact::<B, C, _>(
+        artifact_dir,
+        |config, device| config.cnn().init(device),
+        &device,
+    ) {
+        Ok(artifact) => artifact,
+        Err(err) => {
+            eprintln!("Could not load the trained model: {err}");
+            return;
+        }
+    };
+
+    let data = config.data();
+    let batcher = data.batcher::<B>(device);
+    let names = || {
+        data.dataset
+            .class_names()
+            .into_iter()
+            .map(String::from)
+            .collect()
+    };
+    let test = match (&data.image_folder, data.dataset.mnist()) {
+        (Some(folder), _) => {
+            let dataset = data.folder_dataset(folder, Split::Test);
+            let names = dataset.classes().to_vec();
+            dataset
+                .get(index)
+                .map(|item| (batcher.batch(vec![item]), names))
+        }
+        (None, Some(variant)) => data
+            .mnist_dataset(variant, Split::Test)
+            .get(index)
+            .map(|item| (batcher.batch(vec![item]), names())),
+        (None, None) => data
+            .cifar_dataset(Split::Test)
+            .get(index)
+            .map(|item| (batcher.batch(vec![item]), names())),
+    };
+    let Some((batch, names)) = test else {
+        eprintln!("The test set has no item {index}");
+        return;
+    };
+
+    let label = batch.targets.into_scalar().elem::<i64>() as usize;
+    let output = model.forward(batch.images);
+    let predicted = output
+        .argmax(1)
+        .flatten::<1>(0, 1)
+        .into_scalar()
+        .elem::<i64>() as usize;
+
+    println!("Predicted {} Expected {}", names[predicted], names[label]);
+}
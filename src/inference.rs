@@ -0,0 +1,162 @@
+use std::fmt::Display;
+use std::path::PathBuf;
+
+use burn::{
+    config::{Config, ConfigError},
+    module::{Module, ModuleVisitor, ParamId},
+    record::{CompactRecorder, Recorder, RecorderError},
+    tensor::{backend::Backend, Bool, Int, Tensor},
+};
+
+#[derive(Debug)]
+pub enum LoadError {
+    MissingConfig {
+        path: PathBuf,
+    },
+    InvalidConfig {
+        path: PathBuf,
+        message: String,
+    },
+    MissingRecord {
+        path: PathBuf,
+    },
+    CorruptRecord {
+        path: PathBuf,
+        message: String,
+    },
+    ShapeMismatch {
+        parameter: String,
+        expected: Vec<usize>,
+        found: Vec<usize>,
+    },
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadError::MissingConfig { path } => {
+                write!(f, "Config {} does not exist", path.display())
+            }
+            LoadError::InvalidConfig { path, message } => {
+                write!(f, "Config {} is invalid: {message}", path.display())
+            }
+            LoadError::MissingRecord { path } => {
+                write!(f, "Trained model {} does not exist", path.display())
+            }
+            LoadError::CorruptRecord { path, message } => {
+                write!(f, "Trained model {} is corrupt: {message}", path.display())
+            }
+            LoadError::ShapeMismatch {
+                parameter,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Parameter {parameter} has shape {found:?} in the record but {expected:?} in the config"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+/// Loads the `config.json` saved in the artifact directory.
+pub fn load_config<C: Config>(artifact_dir: &str) -> Result<C, LoadError> {
+    let path = PathBuf::from(format!("{artifact_dir}/config.json"));
+
+    C::load(&path).map_err(|err| match err {
+        ConfigError::FileNotFound(_) => LoadError::MissingConfig { path },
+        ConfigError::InvalidFormat(message) => LoadError::InvalidConfig { path, message },
+    })
+}
+
+/// Loads the trained weights saved in the artifact directory into `model`, checking
+/// that every parameter of the record has the shape the model was initialized with.
+pub fn load_model<B: Backend, M: Module<B>>(
+    artifact_dir: &str,
+    model: M,
+    device: &B::Device,
+) -> Result<M, LoadError> {
+    let path = PathBuf::from(format!("{artifact_dir}/model"));
+
+    let record = CompactRecorder::new()
+        .load(path.clone(), device)
+        .map_err(|err| match err {
+            RecorderError::FileNotFound(_) => LoadError::MissingRecord { path: path.clone() },
+            RecorderError::DeserializeError(message) | RecorderError::Unknown(message) => {
+                LoadError::CorruptRecord {
+                    path: path.clone(),
+                    message,
+                }
+            }
+        })?;
+
+    let expected = shapes(&model);
+    let model = model.load_record(record);
+    let found = shapes(&model);
+
+    if expected.len() != found.len() {
+        return Err(LoadError::CorruptRecord {
+            path,
+            message: format!(
+                "expected {} parameters, found {}",
+                expected.len(),
+                found.len()
+            ),
+        });
+    }
+
+    for ((_, expected), (id, found)) in expected.into_iter().zip(found) {
+        if expected != found {
+            return Err(LoadError::ShapeMismatch {
+                parameter: id,
+                expected,
+                found,
+            });
+        }
+    }
+
+    Ok(model)
+}
+
+/// Loads both the config and the trained model of an artifact directory, `init`
+/// builds the untrained model described by the config.
+pub fn load_artifact<B, C, M>(
+    artifact_dir: &str,
+    init: impl FnOnce(&C, &B::Device) -> M,
+    device: &B::Device,
+) -> Result<(C, M), LoadError>
+where
+    B: Backend,
+    C: Config,
+    M: Module<B>,
+{
+    let config = load_config::<C>(artifact_dir)?;
+    let model = load_model(artifact_dir, init(&config, device), device)?;
+
+    Ok((config, model))
+}
+
+fn shapes<B: Backend, M: Module<B>>(model: &M) -> Vec<(String, Vec<usize>)> {
+    let mut collector = ShapeCollector { shapes: Vec::new() };
+    model.visit(&mut collector);
+    collector.shapes
+}
+
+struct ShapeCollector {
+    shapes: Vec<(String, Vec<usize>)>,
+}
+
+impl<B: Backend> ModuleVisitor<B> for ShapeCollector {
+    fn visit_float<const D: usize>(&mut self, id: &ParamId, tensor: &Tensor<B, D>) {
+        self.shapes.push((id.to_string(), tensor.dims().to_vec()));
+    }
+
+    fn visit_int<const D: usize>(&mut self, id: &ParamId, tensor: &Tensor<B, D, Int>) {
+        self.shapes.push((id.to_string(), tensor.dims().to_vec()));
+    }
+
+    fn visit_bool<const D: usize>(&mut self, id: &ParamId, tensor: &Tensor<B, D, Bool>) {
+        self.shapes.push((id.to_string(), tensor.dims().to_vec()));
+    }
+}
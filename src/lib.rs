@@ -1,3 +1,31 @@
+pub mod augmentation;
+pub mod backend;
+pub mod blobs_data;
+pub mod cifar_data;
+pub mod circles_data;
+pub mod cli;
+pub mod csv_data;
+pub mod curve_data;
+pub mod early_stopping;
+pub mod evaluation;
+pub mod folder_data;
+pub mod history;
+pub mod image_experiment;
+pub mod inference;
 pub mod mist_data;
+pub mod mlp_experiment;
+pub mod model;
 pub mod moons_data;
+pub mod optimizer;
+pub mod overrides;
+mod report;
+pub mod runs;
+pub mod scheduler;
+pub mod spirals_data;
+pub mod split;
+pub mod sweep;
 pub mod toy_data;
+pub mod train;
+pub mod transform;
+pub mod visualization;
+pub mod xor_data;
@@ -3,43 +3,75 @@ use burn::{
     prelude::*,
 };
 
+use crate::augmentation::Augmenter;
+use crate::cifar_data::binary::CifarItem;
+use crate::folder_data::data::ImageFolderItem;
+use crate::transform::{Image, TransformPipeline};
+
 #[derive(Clone)]
 pub struct MnistBatcher<B: Backend> {
     device: B::Device,
+    transform: TransformPipeline,
+    augmenter: Option<Augmenter>,
 }
 
 impl<B: Backend> MnistBatcher<B> {
+    /// Batcher normalizing the images with the MNIST statistics.
     pub fn new(device: B::Device) -> Self {
-        Self { device }
+        Self {
+            device,
+            transform: TransformPipeline::mnist(),
+            augmenter: None,
+        }
+    }
+
+    /// Replaces the transforms applied to each image, e.g. to normalize another
+    /// grayscale dataset with its own statistics.
+    pub fn with_transform(mut self, transform: TransformPipeline) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    /// Augments the images of each batch before their transforms, for the batcher of
+    /// the training data only.
+    pub fn with_augmenter(mut self, augmenter: Augmenter) -> Self {
+        self.augmenter = Some(augmenter);
+        self
     }
 }
 
 #[derive(Clone, Debug)]
 pub struct MnistBatch<B: Backend> {
-    pub images: Tensor<B, 3>,
+    /// Images of shape `[batch_size, channels, height, width]`.
+    pub images: Tensor<B, 4>,
     pub targets: Tensor<B, 1, Int>,
 }
 
-impl<B: Backend> Batcher<MnistItem, MnistBatch<B>> for MnistBatcher<B> {
-    fn batch(&self, items: Vec<MnistItem>) -> MnistBatch<B> {
-        let images = items
+impl<B: Backend> MnistBatcher<B> {
+    fn batch_images(&self, items: Vec<(Image, i64)>) -> MnistBatch<B> {
+        let targets = items
             .iter()
-            .map(|item| Data::<f32, 2>::from(item.image))
-            .map(|data| Tensor::<B, 2>::from_data(data.convert(), &self.device))
-            .map(|tensor| tensor.reshape([1, 28, 28]))
-            // Normalize: make between [0,1] and make the mean=0 and std=1
-            // values mean=0.1307,std=0.3081 are from the PyTorch MNIST example
-            // https://github.com/pytorch/examples/blob/54f4572509891883a947411fd7239237dd2a39c3/mnist/main.py#L122
-            .map(|tensor| ((tensor / 255) - 0.1307) / 0.3081)
+            .map(|&(_, label)| {
+                Tensor::<B, 1, Int>::from_data(Data::from([label.elem()]), &self.device)
+            })
             .collect();
 
-        let targets = items
-            .iter()
-            .map(|item| {
-                Tensor::<B, 1, Int>::from_data(
-                    Data::from([(item.label as i64).elem()]),
-                    &self.device,
-                )
+        let images: Vec<Image> = items.into_iter().map(|(image, _)| image).collect();
+        let images = match &self.augmenter {
+            Some(augmenter) => augmenter.apply_batch(images),
+            None => images,
+        };
+
+        let images = images
+            .into_iter()
+            .map(|image| {
+                self.transform
+                    .apply(image)
+                    .unwrap_or_else(|err| panic!("Could not transform the image: {err}"))
+            })
+            .map(|image| {
+                let shape = Shape::new([1, image.channels, image.height, image.width]);
+                Tensor::<B, 4>::from_data(Data::new(image.data, shape).convert(), &self.device)
             })
             .collect();
 
@@ -49,3 +81,40 @@ impl<B: Backend> Batcher<MnistItem, MnistBatch<B>> for MnistBatcher<B> {
         MnistBatch { images, targets }
     }
 }
+
+impl<B: Backend> Batcher<MnistItem, MnistBatch<B>> for MnistBatcher<B> {
+    fn batch(&self, items: Vec<MnistItem>) -> MnistBatch<B> {
+        self.batch_images(
+            items
+                .iter()
+                .map(|item| (Image::from(&item.image), item.label as i64))
+                .collect(),
+        )
+    }
+}
+
+/// Batches images of any size and channel count, e.g. from an image folder whose
+/// images fit the model.
+impl<B: Backend> Batcher<ImageFolderItem, MnistBatch<B>> for MnistBatcher<B> {
+    fn batch(&self, items: Vec<ImageFolderItem>) -> MnistBatch<B> {
+        self.batch_images(
+            items
+                .into_iter()
+                .map(|item| (item.image, item.label as i64))
+                .collect(),
+        )
+    }
+}
+
+/// Batches the RGB images of CIFAR-10, transformed with `cifar_data::data::transform`
+/// for the normalization of its channels.
+impl<B: Backend> Batcher<CifarItem, MnistBatch<B>> for MnistBatcher<B> {
+    fn batch(&self, items: Vec<CifarItem>) -> MnistBatch<B> {
+        self.batch_images(
+            items
+                .into_iter()
+                .map(|item| (item.image, item.label as i64))
+                .collect(),
+        )
+    }
+}
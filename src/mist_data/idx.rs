@@ -0,0 +1,318 @@
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+
+use burn::data::dataset::{vision::MnistItem, Dataset};
+use flate2::read::GzDecoder;
+
+use crate::split::Split;
+
+const TRAIN_IMAGES: &str = "train-images-idx3-ubyte";
+const TRAIN_LABELS: &str = "train-labels-idx1-ubyte";
+const TEST_IMAGES: &str = "t10k-images-idx3-ubyte";
+const TEST_LABELS: &str = "t10k-labels-idx1-ubyte";
+
+// Magic numbers of unsigned byte IDX files with 3 (images) and 1 (labels) dimensions
+const IMAGES_MAGIC: u32 = 0x0000_0803;
+const LABELS_MAGIC: u32 = 0x0000_0801;
+
+const WIDTH: usize = 28;
+const HEIGHT: usize = 28;
+
+#[derive(Debug)]
+pub enum IdxError {
+    MissingFile {
+        dir: PathBuf,
+        name: String,
+    },
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    BadMagic {
+        path: PathBuf,
+        expected: u32,
+        found: u32,
+    },
+    BadDimensions {
+        path: PathBuf,
+        expected: Vec<usize>,
+        found: Vec<usize>,
+    },
+    Truncated {
+        path: PathBuf,
+        expected: usize,
+        found: usize,
+    },
+    /// The file has more bytes than its dimensions give.
+    TrailingData {
+        path: PathBuf,
+        expected: usize,
+        found: usize,
+    },
+    CountMismatch {
+        images: usize,
+        labels: usize,
+    },
+    /// The dataset has no download, only MNIST does.
+    NoDirectory {
+        dataset: String,
+    },
+    /// The dataset only comes with training and test files.
+    NoSplit {
+        dataset: String,
+        split: Split,
+    },
+    BadLabel {
+        index: usize,
+        label: u8,
+        min: usize,
+        max: usize,
+    },
+}
+
+impl Display for IdxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IdxError::MissingFile { dir, name } => write!(
+                f,
+                "Neither {name} nor {name}.gz was found in {}",
+                dir.display()
+            ),
+            IdxError::Io { path, source } => {
+                write!(f, "Could not read {}: {source}", path.display())
+            }
+            IdxError::BadMagic {
+                path,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Bad magic number in {}: expected {expected:#010x}, found {found:#010x}",
+                path.display()
+            ),
+            IdxError::BadDimensions {
+                path,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Bad dimensions in {}: expected {expected:?}, found {found:?}",
+                path.display()
+            ),
+            IdxError::Truncated {
+                path,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Truncated data in {}: expected {expected} bytes, found {found}",
+                path.display()
+            ),
+            IdxError::TrailingData {
+                path,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Trailing data in {}: expected {expected} bytes, found {found}",
+                path.display()
+            ),
+            IdxError::CountMismatch { images, labels } => {
+                write!(f, "Found {images} images but {labels} labels")
+            }
+            IdxError::NoDirectory { dataset } => write!(
+                f,
+                "{dataset} can only be read from local IDX files, no directory was given"
+            ),
+            IdxError::NoSplit { dataset, split } => write!(f, "{dataset} has no {split} split"),
+            IdxError::BadLabel {
+                index,
+                label,
+                min,
+                max,
+            } => write!(
+                f,
+                "Item {index} has label {label}, expected a label from {min} to {max}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for IdxError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            IdxError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Unsigned byte array stored in the IDX format used by the MNIST files.
+#[derive(Clone, Debug)]
+pub struct IdxArray {
+    pub dims: Vec<usize>,
+    pub data: Vec<u8>,
+}
+
+impl IdxArray {
+    /// Reads an IDX file, decompressing it when its name ends with `.gz`.
+    pub fn read(path: &Path, magic: u32) -> Result<Self, IdxError> {
+        let io_error = |source| IdxError::Io {
+            path: path.to_path_buf(),
+            source,
+        };
+
+        let file = BufReader::new(File::open(path).map_err(io_error)?);
+        let mut reader: Box<dyn Read> = match path.extension() {
+            Some(extension) if extension == "gz" => Box::new(GzDecoder::new(file)),
+            _ => Box::new(file),
+        };
+
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).map_err(io_error)?;
+
+        let truncated = |expected| IdxError::Truncated {
+            path: path.to_path_buf(),
+            expected,
+            found: bytes.len(),
+        };
+
+        let word = |i: usize| {
+            bytes
+                .get(4 * i..4 * (i + 1))
+                .map(|word| u32::from_be_bytes([word[0], word[1], word[2], word[3]]))
+        };
+
+        let found = word(0).ok_or_else(|| truncated(4))?;
+        if found != magic {
+            return Err(IdxError::BadMagic {
+                path: path.to_path_buf(),
+                expected: magic,
+                found,
+            });
+        }
+
+        // The last byte of the magic number is the number of dimensions
+        let num_dims = (magic & 0xff) as usize;
+        let header = 4 * (num_dims + 1);
+        let dims = (1..=num_dims)
+            .map(|i| word(i).map(|dim| dim as usize))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| truncated(header))?;
+
+        let expected = header + dims.iter().product::<usize>();
+        if bytes.len() < expected {
+            return Err(truncated(expected));
+        }
+        if bytes.len() > expected {
+            return Err(IdxError::TrailingData {
+                path: path.to_path_buf(),
+                expected,
+                found: bytes.len(),
+            });
+        }
+
+        Ok(Self {
+            dims,
+            data: bytes[header..].to_vec(),
+        })
+    }
+}
+
+/// MNIST read from the original IDX files in a local directory, so no download is needed.
+/// Each file may be stored either raw or gzip compressed.
+pub struct MnistIdxDataset {
+    dataset: Vec<MnistItem>,
+}
+
+impl Dataset<MnistItem> for MnistIdxDataset {
+    fn get(&self, index: usize) -> Option<MnistItem> {
+        self.dataset.get(index).cloned()
+    }
+
+    fn len(&self) -> usize {
+        self.dataset.len()
+    }
+}
+
+impl MnistIdxDataset {
+    pub fn train(dir: impl AsRef<Path>) -> Result<Self, IdxError> {
+        Self::from_dir(dir, TRAIN_IMAGES, TRAIN_LABELS)
+    }
+
+    pub fn test(dir: impl AsRef<Path>) -> Result<Self, IdxError> {
+        Self::from_dir(dir, TEST_IMAGES, TEST_LABELS)
+    }
+
+    /// Reads the images and labels files with the given names, without their optional
+    /// `.gz` extension, from `dir`.
+    pub fn from_dir(dir: impl AsRef<Path>, images: &str, labels: &str) -> Result<Self, IdxError> {
+        let dir = dir.as_ref();
+        Self::from_files(&find_file(dir, images)?, &find_file(dir, labels)?)
+    }
+
+    pub fn from_files(images: &Path, labels: &Path) -> Result<Self, IdxError> {
+        let images_array = IdxArray::read(images, IMAGES_MAGIC)?;
+        let labels_array = IdxArray::read(labels, LABELS_MAGIC)?;
+
+        let num_images = images_array.dims[0];
+        if images_array.dims[1..] != [HEIGHT, WIDTH] {
+            return Err(IdxError::BadDimensions {
+                path: images.to_path_buf(),
+                expected: vec![num_images, HEIGHT, WIDTH],
+                found: images_array.dims,
+            });
+        }
+
+        if num_images != labels_array.dims[0] {
+            return Err(IdxError::CountMismatch {
+                images: num_images,
+                labels: labels_array.dims[0],
+            });
+        }
+
+        let dataset = images_array
+            .data
+            .chunks_exact(WIDTH * HEIGHT)
+            .zip(labels_array.data)
+            .map(|(pixels, label)| {
+                let mut image = [[0f32; WIDTH]; HEIGHT];
+                for (i, pixel) in pixels.iter().enumerate() {
+                    image[i / WIDTH][i % WIDTH] = *pixel as f32;
+                }
+                MnistItem { image, label }
+            })
+            .collect();
+
+        Ok(Self { dataset })
+    }
+
+    /// Applies `f` to every item and its index, e.g. to fix the orientation or check
+    /// the labels of a variant, stopping at the first error.
+    pub fn try_map(
+        self,
+        mut f: impl FnMut(usize, MnistItem) -> Result<MnistItem, IdxError>,
+    ) -> Result<Self, IdxError> {
+        let dataset = self
+            .dataset
+            .into_iter()
+            .enumerate()
+            .map(|(index, item)| f(index, item))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { dataset })
+    }
+}
+
+fn find_file(dir: &Path, name: &str) -> Result<PathBuf, IdxError> {
+    [name.to_string(), format!("{name}.gz")]
+        .into_iter()
+        .map(|file| dir.join(file))
+        .find(|path| path.is_file())
+        .ok_or_else(|| IdxError::MissingFile {
+            dir: dir.to_path_buf(),
+            name: name.to_string(),
+        })
+}
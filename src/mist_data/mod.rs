@@ -1 +1,3 @@
 pub mod data;
+pub mod idx;
+pub mod variants;
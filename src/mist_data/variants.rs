@@ -0,0 +1,221 @@
+use burn::config::Config;
+use burn::data::dataset::{
+    vision::{MnistDataset, MnistItem},
+    Dataset,
+};
+
+use super::idx::{IdxError, MnistIdxDataset};
+use crate::split::Split;
+use crate::transform::{ImageStatistics, Transform, TransformPipeline};
+
+const DIGITS: [&str; 10] = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+
+const FASHION: [&str; 10] = [
+    "T-shirt/top",
+    "Trouser",
+    "Pullover",
+    "Dress",
+    "Coat",
+    "Sandal",
+    "Shirt",
+    "Sneaker",
+    "Bag",
+    "Ankle boot",
+];
+
+// Hiragana of the Kuzushiji-MNIST classes: o, ki, su, tsu, na, ha, ma, ya, re, wo
+const KUZUSHIJI: [&str; 10] = ["お", "き", "す", "つ", "な", "は", "ま", "や", "れ", "を"];
+
+const UPPERCASE: [&str; 26] = [
+    "A", "B", "C", "D", "E", "F", "G", "H", "I", "J", "K", "L", "M", "N", "O", "P", "Q", "R", "S",
+    "T", "U", "V", "W", "X", "Y", "Z",
+];
+
+const LOWERCASE: [&str; 26] = [
+    "a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k", "l", "m", "n", "o", "p", "q", "r", "s",
+    "t", "u", "v", "w", "x", "y", "z",
+];
+
+// Lowercase letters kept apart from their uppercase in the merged EMNIST splits
+const MERGED_LOWERCASE: [&str; 11] = ["a", "b", "d", "e", "f", "g", "h", "n", "q", "r", "t"];
+
+/// Splits of EMNIST, see https://www.nist.gov/itl/products-and-services/emnist-dataset.
+#[derive(Config, Debug, Copy, PartialEq, Eq)]
+pub enum EmnistSplit {
+    /// 47 balanced classes of digits and letters.
+    Balanced,
+    /// 62 unbalanced classes, digits then uppercase then lowercase letters.
+    ByClass,
+    /// 47 unbalanced classes, letters with similar cases are merged.
+    ByMerge,
+    Digits,
+    /// 26 classes, each merging the uppercase and lowercase letter.
+    Letters,
+    /// Same digits as MNIST, drawn from the EMNIST writers.
+    Mnist,
+}
+
+impl EmnistSplit {
+    fn name(&self) -> &'static str {
+        match self {
+            EmnistSplit::Balanced => "balanced",
+            EmnistSplit::ByClass => "byclass",
+            EmnistSplit::ByMerge => "bymerge",
+            EmnistSplit::Digits => "digits",
+            EmnistSplit::Letters => "letters",
+            EmnistSplit::Mnist => "mnist",
+        }
+    }
+}
+
+/// Datasets with the MNIST format of 28x28 grayscale images, so they all work with
+/// the `MnistBatcher` and the chapter 3 models.
+#[derive(Config, Debug, Copy, PartialEq, Eq)]
+pub enum MnistVariant {
+    Mnist,
+    /// Zalando's clothing images, https://github.com/zalandoresearch/fashion-mnist.
+    FashionMnist,
+    /// Cursive Japanese characters, https://github.com/rois-codh/kmnist.
+    Kmnist,
+    /// Handwritten digits and letters, read from the `emnist-{split}-*` files.
+    Emnist {
+        split: EmnistSplit,
+    },
+}
+
+impl MnistVariant {
+    /// Reads the training split from the IDX files in `dir`, raw or gzip compressed.
+    pub fn train(&self, dir: &str) -> Result<MnistIdxDataset, IdxError> {
+        self.read(dir, Split::Train)
+    }
+
+    /// Reads the test split from the IDX files in `dir`, raw or gzip compressed.
+    pub fn test(&self, dir: &str) -> Result<MnistIdxDataset, IdxError> {
+        self.read(dir, Split::Test)
+    }
+
+    /// Reads the training or test split from `dir` when given. Otherwise only MNIST is
+    /// available, downloaded by burn. None of the datasets has a validation split.
+    pub fn dataset(
+        &self,
+        dir: Option<&str>,
+        split: Split,
+    ) -> Result<Box<dyn Dataset<MnistItem>>, IdxError> {
+        match (dir, self, split) {
+            (_, _, Split::Valid) => Err(self.no_split(split)),
+            (Some(dir), _, _) => Ok(Box::new(self.read(dir, split)?)),
+            (None, MnistVariant::Mnist, Split::Train) => Ok(Box::new(MnistDataset::train())),
+            (None, MnistVariant::Mnist, _) => Ok(Box::new(MnistDataset::test())),
+            (None, _, _) => Err(IdxError::NoDirectory {
+                dataset: self.to_string(),
+            }),
+        }
+    }
+
+    /// Mean and standard deviation of the training pixels scaled to `[0, 1]`.
+    pub fn statistics(&self) -> ImageStatistics {
+        let (mean, std) = match self {
+            MnistVariant::Mnist => (0.1307, 0.3081),
+            MnistVariant::FashionMnist => (0.2860, 0.3530),
+            MnistVariant::Kmnist => (0.1918, 0.3483),
+            // Statistics of the balanced split, the other splits are within 0.01
+            MnistVariant::Emnist { .. } => (0.1751, 0.3332),
+        };
+
+        ImageStatistics::new(vec![mean], vec![std])
+    }
+
+    /// Scales the pixels to `[0, 1]` then standardizes them with [`Self::statistics`].
+    pub fn transform(&self) -> TransformPipeline {
+        TransformPipeline::new(vec![Transform::Scale {
+            factor: 1.0 / 255.0,
+        }])
+        .normalize(&self.statistics())
+    }
+
+    /// Name of each class, indexed by label.
+    pub fn class_names(&self) -> Vec<&'static str> {
+        match self {
+            MnistVariant::Mnist => DIGITS.to_vec(),
+            MnistVariant::FashionMnist => FASHION.to_vec(),
+            MnistVariant::Kmnist => KUZUSHIJI.to_vec(),
+            MnistVariant::Emnist { split } => match split {
+                EmnistSplit::Digits | EmnistSplit::Mnist => DIGITS.to_vec(),
+                EmnistSplit::Letters => UPPERCASE.to_vec(),
+                EmnistSplit::ByClass => [&DIGITS[..], &UPPERCASE[..], &LOWERCASE[..]].concat(),
+                EmnistSplit::Balanced | EmnistSplit::ByMerge => {
+                    [&DIGITS[..], &UPPERCASE[..], &MERGED_LOWERCASE[..]].concat()
+                }
+            },
+        }
+    }
+
+    pub fn num_classes(&self) -> usize {
+        self.class_names().len()
+    }
+
+    fn read(&self, dir: &str, split: Split) -> Result<MnistIdxDataset, IdxError> {
+        let (prefix, split) = match (self, split) {
+            (_, Split::Valid) => return Err(self.no_split(split)),
+            (MnistVariant::Emnist { split: emnist }, _) => {
+                (format!("emnist-{}-", emnist.name()), split.name())
+            }
+            (_, Split::Train) => (String::new(), "train"),
+            (_, Split::Test) => (String::new(), "t10k"),
+        };
+
+        let dataset = MnistIdxDataset::from_dir(
+            dir,
+            &format!("{prefix}{split}-images-idx3-ubyte"),
+            &format!("{prefix}{split}-labels-idx1-ubyte"),
+        )?;
+
+        // EMNIST letters are labelled from 1
+        let min = match self {
+            MnistVariant::Emnist {
+                split: EmnistSplit::Letters,
+            } => 1,
+            _ => 0,
+        };
+        let num_classes = self.num_classes();
+
+        dataset.try_map(|index, item| {
+            let label = item
+                .label
+                .checked_sub(min as u8)
+                .filter(|&label| (label as usize) < num_classes)
+                .ok_or(IdxError::BadLabel {
+                    index,
+                    label: item.label,
+                    min,
+                    max: min + num_classes - 1,
+                })?;
+
+            Ok(match self {
+                MnistVariant::Emnist { .. } => MnistItem {
+                    image: transpose(&item.image),
+                    label,
+                },
+                _ => MnistItem { label, ..item },
+            })
+        })
+    }
+
+    fn no_split(&self, split: Split) -> IdxError {
+        IdxError::NoSplit {
+            dataset: self.to_string(),
+            split,
+        }
+    }
+}
+
+// EMNIST images are stored transposed
+fn transpose(image: &[[f32; 28]; 28]) -> [[f32; 28]; 28] {
+    let mut transposed = [[0.0; 28]; 28];
+    for (y, row) in transposed.iter_mut().enumerate() {
+        for (x, pixel) in row.iter_mut().enumerate() {
+            *pixel = image[x][y];
+        }
+    }
+    transposed
+}
@@ -0,0 +1,236 @@
+use burn::{
+    config::Config,
+    data::dataloader::batcher::Batcher,
+    tensor::backend::{AutodiffBackend, Backend},
+};
+use plotly::{color::NamedColor, common::Marker, common::Mode, Plot, Scatter};
+
+use crate::evaluation::{evaluate_classifier, evaluate_regressor, Evaluation};
+use crate::inference::{load_artifact, LoadError};
+use crate::model::mlp::{Mlp, MlpConfig};
+use crate::moons_data::batcher::MoonsBatcher;
+use crate::moons_data::data::{MoonDatasetConfig, MoonsConfig};
+use crate::toy_data::data::{make_toydata, ToyBatcher, ToyDatasetConfig};
+use crate::train::{train_classifier, train_regressor, ArtifactConfig, TrainingConfig};
+use crate::visualization::DecisionBoundaryConfig;
+
+/// Data of the chapter 2 experiments, which decides whether the MLP is trained as a
+/// regressor or as a classifier.
+#[derive(Config, Debug)]
+pub enum MlpDataConfig {
+    /// Regression of the toy curve, the MLP has one input and one output.
+    Toy(ToyDatasetConfig),
+    /// Classification of the two moons, the MLP has two inputs and two outputs.
+    Moons(MoonDatasetConfig),
+}
+
+/// Config of a chapter 2 experiment, saved as `config.json` in the artifact directory.
+#[derive(Config)]
+pub struct MlpExperimentConfig {
+    pub model: MlpConfig,
+    pub training: TrainingConfig,
+    pub data: MlpDataConfig,
+}
+
+impl ArtifactConfig for MlpExperimentConfig {
+    fn training(&self) -> &TrainingConfig {
+        &self.training
+    }
+
+    fn dataset(&self) -> String {
+        format!("{:?}", self.data)
+    }
+}
+
+/// Trains the model on the training split, validated on the validation split.
+pub fn train<B: AutodiffBackend>(
+    artifact_dir: &str,
+    config: &MlpExperimentConfig,
+    device: B::Device,
+    checkpoint: Option<usize>,
+) {
+    let training = &config.training;
+    let model = |device: &B::Device| config.model.init::<B>(device);
+
+    match &config.data {
+        MlpDataConfig::Toy(data) => {
+            let dataloader_train =
+                training.dataloader(ToyBatcher::<B>::new(device.clone()), data.train());
+            let dataloader_valid = training.dataloader(
+                ToyBatcher::<B::InnerBackend>::new(device.clone()),
+                data.valid(),
+            );
+
+            train_regressor(
+                artifact_dir,
+                config,
+                model,
+                dataloader_train,
+                dataloader_valid,
+                device,
+                checkpoint,
+            );
+        }
+        MlpDataConfig::Moons(data) => {
+            let dataloader_train =
+                training.dataloader(MoonsBatcher::<B>::new(device.clone()), data.train());
+            let dataloader_valid = training.dataloader(
+                MoonsBatcher::<B::InnerBackend>::new(device.clone()),
+                data.valid(),
+            );
+
+            train_classifier(
+                artifact_dir,
+                config,
+                model,
+                dataloader_train,
+                dataloader_valid,
+                device,
+                checkpoint,
+            );
+        }
+    }
+}
+
+/// Metrics of the trained model on the test split.
+pub fn evaluate<B: Backend>(
+    artifact_dir: &str,
+    device: B::Device,
+) -> Result<Evaluation, LoadError> {
+    let (config, model) = load::<B>(artifact_dir, &device)?;
+    let training = &config.training;
+
+    Ok(match &config.data {
+        MlpDataConfig::Toy(data) => evaluate_regressor(
+            &model,
+            training.dataloader(ToyBatcher::<B>::new(device), data.test()),
+        ),
+        MlpDataConfig::Moons(data) => evaluate_classifier(
+            &model,
+            training.dataloader(MoonsBatcher::<B>::new(device), data.test()),
+        ),
+    })
+}
+
+/// Plots the predictions of the trained model as `model.html` in the artifact
+/// directory: the fitted curve over the toy samples, or the predicted class of
+/// fresh moons next to their decision boundary.
+pub fn infer<B: Backend>(artifact_dir: &str, device: B::Device) {
+    let (config, model) = match load::<B>(artifact_dir, &device) {
+        Ok(artifact) => artifact,
+        Err(err) => {
+            eprintln!("Could not load the trained model: {err}");
+            return;
+        }
+    };
+
+    let mut plot = match &config.data {
+        MlpDataConfig::Toy(data) => plot_curve(&model, data, device),
+        MlpDataConfig::Moons(data) => plot_moons(artifact_dir, &model, data, device),
+    };
+
+    plot.use_local_plotly();
+    plot.write_html(format!("{artifact_dir}/model.html"));
+}
+
+fn load<B: Backend>(
+    artifact_dir: &str,
+    device: &B::Device,
+) -> Result<(MlpExperimentConfig, Mlp<B>), LoadError> {
+    load_artifact::<B, MlpExperimentConfig, _>(
+        artifact_dir,
+        |config, device| config.model.init(device),
+        device,
+    )
+}
+
+fn plot_curve<B: Backend>(model: &Mlp<B>, data: &ToyDatasetConfig, device: B::Device) -> Plot {
+    let mut plot = Plot::new();
+
+    let items = make_toydata(data.start, data.end, data.n);
+
+    let x: Vec<f32> = items.iter().map(|item| item.x).collect();
+    let y: Vec<f32> = items.iter().map(|item| item.y).collect();
+
+    let trace = Scatter::new(x.clone(), y).mode(Mode::Markers);
+    plot.add_trace(trace);
+
+    let batch = ToyBatcher::<B>::new(device).batch(items);
+    let y = model.forward(batch.x);
+
+    let y = y.flatten::<1>(0, 1).to_data();
+    let y = y.convert::<f32>().value;
+
+    let trace = Scatter::new(x, y).mode(Mode::Markers);
+    plot.add_trace(trace);
+
+    plot
+}
+
+fn plot_moons<B: Backend>(
+    artifact_dir: &str,
+    model: &Mlp<B>,
+    data: &MoonDatasetConfig,
+    device: B::Device,
+) -> Plot {
+    let mut plot = Plot::new();
+
+    // Fewer moons than the dataset, drawn the same way
+    let items = MoonsConfig {
+        n_samples_out: 100,
+        n_samples_in: 100,
+        ..data.moons.clone()
+    }
+    .generate();
+
+    let x1: Vec<f32> = items.iter().map(|item| item.x[0]).collect();
+    let x2: Vec<f32> = items.iter().map(|item| item.x[1]).collect();
+    let y: Vec<NamedColor> = items
+        .iter()
+        .map(|item| match item.y {
+            0 => NamedColor::Red,
+            _ => NamedColor::Blue,
+        })
+        .collect();
+
+    let trace = Scatter::new(x1.clone(), x2.clone())
+        .name("True class")
+        .mode(Mode::Markers)
+        .marker(Marker::new().color_array(y));
+    plot.add_trace(trace);
+
+    let points: Vec<[f32; 2]> = items.iter().map(|item| item.x).collect();
+    let labels: Vec<usize> = items.iter().map(|item| item.y as usize).collect();
+
+    let batch = MoonsBatcher::<B>::new(device.clone()).batch(items);
+
+    DecisionBoundaryConfig::new().write::<B, _>(
+        artifact_dir,
+        |x| model.forward(x),
+        &points,
+        &labels,
+        &device,
+    );
+
+    let y = model.forward(batch.x);
+    let y = y
+        .argmax(1)
+        .flatten::<1>(0, 1)
+        .to_data()
+        .convert::<i8>()
+        .value
+        .iter()
+        .map(|y| match y {
+            1 => NamedColor::Red,
+            _ => NamedColor::Blue,
+        })
+        .collect();
+
+    let trace = Scatter::new(x1, x2)
+        .name("Predicted class")
+        .mode(Mode::Markers)
+        .marker(Marker::new().color_array(y));
+    plot.add_trace(trace);
+
+    plot
+}
@@ -0,0 +1,255 @@
+use burn::{
+    nn::{
+        conv::{Conv2d, Conv2dConfig},
+        loss::CrossEntropyLoss,
+        pool::{
+            AdaptiveAvgPool2d, AdaptiveAvgPool2dConfig, AvgPool2d, AvgPool2dConfig, MaxPool2d,
+            MaxPool2dConfig,
+        },
+        BatchNorm, BatchNormConfig, GroupNorm, GroupNormConfig, Linear, LinearConfig,
+        PaddingConfig2d,
+    },
+    prelude::*,
+    tensor::backend::AutodiffBackend,
+    train::{ClassificationOutput, TrainOutput, TrainStep, ValidStep},
+};
+
+use super::layers::{Activation, Normalization};
+use crate::mist_data::data::MnistBatch;
+
+/// Pooling at the end of a conv block, with a stride equal to its size.
+#[derive(Config, Debug, Copy, PartialEq, Eq)]
+pub enum Pooling {
+    Max { size: usize },
+    Average { size: usize },
+}
+
+/// How the output of the last conv block is turned into the features of the head.
+#[derive(Config, Debug, Copy, PartialEq, Eq)]
+pub enum Readout {
+    /// Every pixel of every channel, so the head depends on the image size.
+    Flatten,
+    /// Mean of each channel over the image.
+    GlobalAveragePooling,
+}
+
+/// Convolution with a stride of 1, then normalization, activation and pooling.
+#[derive(Config, Debug)]
+pub struct ConvBlockConfig {
+    /// Output channels of the convolution.
+    pub channels: usize,
+    #[config(default = 3)]
+    pub kernel_size: usize,
+    #[config(default = "PaddingConfig2d::Same")]
+    pub padding: PaddingConfig2d,
+    #[config(default = "Activation::Tanh")]
+    pub activation: Activation,
+    pub normalization: Option<Normalization>,
+    pub pooling: Option<Pooling>,
+}
+
+#[derive(Module, Debug)]
+pub struct ConvBlock<B: Backend> {
+    conv: Conv2d<B>,
+    batch_norm: Option<BatchNorm<B, 2>>,
+    layer_norm: Option<GroupNorm<B>>,
+    activation: Activation,
+    max_pool: Option<MaxPool2d>,
+    avg_pool: Option<AvgPool2d>,
+}
+
+impl<B: Backend> ConvBlock<B> {
+    pub fn forward(&self, x: Tensor<B, 4>) -> Tensor<B, 4> {
+        let x = self.conv.forward(x);
+        let x = match (&self.batch_norm, &self.layer_norm) {
+            (Some(norm), _) => norm.forward(x),
+            (_, Some(norm)) => norm.forward(x),
+            (None, None) => x,
+        };
+        let x = self.activation.forward(x);
+        match (&self.max_pool, &self.avg_pool) {
+            (Some(pool), _) => pool.forward(x),
+            (_, Some(pool)) => pool.forward(x),
+            (None, None) => x,
+        }
+    }
+}
+
+impl ConvBlockConfig {
+    pub fn init<B: Backend>(&self, in_channels: usize, device: &B::Device) -> ConvBlock<B> {
+        let size = |size| [size, size];
+
+        ConvBlock {
+            conv: Conv2dConfig::new([in_channels, self.channels], size(self.kernel_size))
+                .with_padding(self.padding.clone())
+                .init(device),
+            batch_norm: match self.normalization {
+                Some(Normalization::Batch) => {
+                    Some(BatchNormConfig::new(self.channels).init(device))
+                }
+                _ => None,
+            },
+            // A single group normalizes over all the channels, like a layer norm
+            layer_norm: match self.normalization {
+                Some(Normalization::Layer) => {
+                    Some(GroupNormConfig::new(1, self.channels).init(device))
+                }
+                _ => None,
+            },
+            activation: self.activation,
+            max_pool: match self.pooling {
+                Some(Pooling::Max { size: pool }) => Some(
+                    MaxPool2dConfig::new(size(pool))
+                        .with_strides(size(pool))
+                        .init(),
+                ),
+                _ => None,
+            },
+            avg_pool: match self.pooling {
+                Some(Pooling::Average { size: pool }) => Some(
+                    AvgPool2dConfig::new(size(pool))
+                        .with_strides(size(pool))
+                        .init(),
+                ),
+                _ => None,
+            },
+        }
+    }
+
+    /// Size of the output for an input of size `[height, width]`, or `None` when the
+    /// block shrinks it to nothing.
+    pub fn output_size(&self, [height, width]: [usize; 2]) -> Option<[usize; 2]> {
+        let [padding_height, padding_width] = match &self.padding {
+            // Same as burn with a stride of 1, which only keeps the size for odd kernels
+            PaddingConfig2d::Same => [self.kernel_size / 2; 2],
+            PaddingConfig2d::Valid => [0, 0],
+            PaddingConfig2d::Explicit(height, width) => [*height, *width],
+        };
+        let conv =
+            |size: usize, padding: usize| (size + 2 * padding + 1).checked_sub(self.kernel_size);
+        let pool = |size: usize| match self.pooling {
+            Some(Pooling::Max { size: pool } | Pooling::Average { size: pool }) => size / pool,
+            None => size,
+        };
+
+        let height = pool(conv(height, padding_height)?);
+        let width = pool(conv(width, padding_width)?);
+        (height > 0 && width > 0).then_some([height, width])
+    }
+}
+
+/// Conv blocks applied in order, then a linear classifier.
+#[derive(Module, Debug)]
+pub struct Cnn<B: Backend> {
+    blocks: Vec<ConvBlock<B>>,
+    global_pool: Option<AdaptiveAvgPool2d>,
+    head: Linear<B>,
+}
+
+#[derive(Config, Debug)]
+pub struct CnnConfig {
+    pub num_classes: usize,
+    pub in_channels: usize,
+    pub image_height: usize,
+    pub image_width: usize,
+    pub blocks: Vec<ConvBlockConfig>,
+    #[config(default = "Readout::Flatten")]
+    pub readout: Readout,
+}
+
+impl CnnConfig {
+    /// Returns the initialized model, with a head sized after the output of the last
+    /// conv block.
+    pub fn init<B: Backend>(&self, device: &B::Device) -> Cnn<B> {
+        let [height, width] = self.output_size();
+        let channels = self.output_channels();
+
+        let mut in_channels = self.in_channels;
+        let blocks = self
+            .blocks
+            .iter()
+            .map(|block| {
+                let conv_block = block.init(in_channels, device);
+                in_channels = block.channels;
+                conv_block
+            })
+            .collect();
+
+        let (global_pool, features) = match self.readout {
+            Readout::Flatten => (None, channels * height * width),
+            Readout::GlobalAveragePooling => {
+                (Some(AdaptiveAvgPool2dConfig::new([1, 1]).init()), channels)
+            }
+        };
+
+        Cnn {
+            blocks,
+            global_pool,
+            head: LinearConfig::new(features, self.num_classes).init(device),
+        }
+    }
+
+    /// Size of the images once through every conv block.
+    pub fn output_size(&self) -> [usize; 2] {
+        self.blocks.iter().enumerate().fold(
+            [self.image_height, self.image_width],
+            |size, (i, block)| {
+                block.output_size(size).unwrap_or_else(|| {
+                    panic!(
+                        "Conv block {i} shrinks {}x{} images to nothing",
+                        size[0], size[1]
+                    )
+                })
+            },
+        )
+    }
+
+    fn output_channels(&self) -> usize {
+        self.blocks
+            .last()
+            .map_or(self.in_channels, |block| block.channels)
+    }
+}
+
+impl<B: Backend> Cnn<B> {
+    // Shapes
+    // - images: [batch_size, channels, height, width]
+    // - output: [batch_size, num_classes]
+    pub fn forward(&self, images: Tensor<B, 4>) -> Tensor<B, 2> {
+        let x = self.blocks.iter().fold(images, |x, block| block.forward(x));
+        let x = match &self.global_pool {
+            Some(pool) => pool.forward(x), // [batch_size, channels, 1, 1]
+            None => x,
+        };
+
+        // Flattens whatever the output shape is, [batch_size, channels * height * width]
+        let x = x.flatten::<2>(1, 3);
+        self.head.forward(x)
+    }
+
+    pub fn forward_classification(
+        &self,
+        images: Tensor<B, 4>,
+        targets: Tensor<B, 1, Int>,
+    ) -> ClassificationOutput<B> {
+        let output = self.forward(images);
+        let loss =
+            CrossEntropyLoss::new(None, &output.device()).forward(output.clone(), targets.clone());
+
+        ClassificationOutput::new(loss, output, targets)
+    }
+}
+
+impl<B: AutodiffBackend> TrainStep<MnistBatch<B>, ClassificationOutput<B>> for Cnn<B> {
+    fn step(&self, batch: MnistBatch<B>) -> TrainOutput<ClassificationOutput<B>> {
+        let item = self.forward_classification(batch.images, batch.targets);
+
+        TrainOutput::new(self, item.loss.backward(), item)
+    }
+}
+
+impl<B: Backend> ValidStep<MnistBatch<B>, ClassificationOutput<B>> for Cnn<B> {
+    fn step(&self, batch: MnistBatch<B>) -> ClassificationOutput<B> {
+        self.forward_classification(batch.images, batch.targets)
+    }
+}
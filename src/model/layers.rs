@@ -0,0 +1,34 @@
+use burn::{constant, prelude::*, tensor::activation};
+
+/// Nonlinearity applied element-wise after each hidden layer or conv block.
+#[derive(Config, Debug, Copy, PartialEq)]
+pub enum Activation {
+    Tanh,
+    Relu,
+    LeakyRelu { negative_slope: f64 },
+    Gelu,
+    Sigmoid,
+}
+
+// Activations have no parameters, so they are saved as empty records
+constant!(Activation);
+
+impl Activation {
+    pub fn forward<B: Backend, const D: usize>(&self, x: Tensor<B, D>) -> Tensor<B, D> {
+        match self {
+            Activation::Tanh => x.tanh(),
+            Activation::Relu => activation::relu(x),
+            Activation::LeakyRelu { negative_slope } => activation::leaky_relu(x, *negative_slope),
+            Activation::Gelu => activation::gelu(x),
+            Activation::Sigmoid => activation::sigmoid(x),
+        }
+    }
+}
+
+/// Normalization of the hidden features, applied before the activation. Layer
+/// normalization of a conv block normalizes each image over all its channels.
+#[derive(Config, Debug, Copy, PartialEq, Eq)]
+pub enum Normalization {
+    Batch,
+    Layer,
+}
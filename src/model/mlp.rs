@@ -0,0 +1,166 @@
+use burn::{
+    nn::{
+        loss::{CrossEntropyLoss, MseLoss, Reduction::Mean},
+        BatchNorm, BatchNormConfig, Dropout, DropoutConfig, LayerNorm, LayerNormConfig, Linear,
+        LinearConfig,
+    },
+    prelude::*,
+    tensor::backend::AutodiffBackend,
+    train::{ClassificationOutput, RegressionOutput, TrainOutput, TrainStep, ValidStep},
+};
+
+use super::layers::{Activation, Normalization};
+use crate::moons_data::batcher::MoonsBatch;
+use crate::toy_data::data::ToyBatch;
+
+/// Hidden layer: linear, normalization, activation then dropout.
+#[derive(Module, Debug)]
+pub struct MlpLayer<B: Backend> {
+    linear: Linear<B>,
+    batch_norm: Option<BatchNorm<B, 0>>,
+    layer_norm: Option<LayerNorm<B>>,
+    activation: Activation,
+    dropout: Option<Dropout>,
+}
+
+impl<B: Backend> MlpLayer<B> {
+    pub fn forward(&self, x: Tensor<B, 2>) -> Tensor<B, 2> {
+        let x = self.linear.forward(x);
+        let x = match (&self.batch_norm, &self.layer_norm) {
+            (Some(norm), _) => norm.forward(x),
+            (_, Some(norm)) => norm.forward(x),
+            (None, None) => x,
+        };
+        let x = self.activation.forward(x);
+        match &self.dropout {
+            Some(dropout) => dropout.forward(x),
+            None => x,
+        }
+    }
+}
+
+/// Fully connected network made of hidden layers followed by a linear head.
+#[derive(Module, Debug)]
+pub struct Mlp<B: Backend> {
+    layers: Vec<MlpLayer<B>>,
+    head: Linear<B>,
+    output: Option<Activation>,
+}
+
+#[derive(Config, Debug)]
+pub struct MlpConfig {
+    pub in_features: usize,
+    /// Width of each hidden layer, a linear model when empty.
+    pub hidden: Vec<usize>,
+    /// Width of the head, e.g. the number of classes.
+    pub out_features: usize,
+    #[config(default = "Activation::Tanh")]
+    pub activation: Activation,
+    /// Probability of zeroing each hidden feature during training.
+    pub dropout: Option<f64>,
+    pub normalization: Option<Normalization>,
+    /// Activation of the head. Classifiers should leave it unset, the cross-entropy
+    /// loss expects logits.
+    pub output: Option<Activation>,
+}
+
+impl MlpConfig {
+    /// Returns the initialized model.
+    pub fn init<B: Backend>(&self, device: &B::Device) -> Mlp<B> {
+        let mut in_features = self.in_features;
+        let layers = self
+            .hidden
+            .iter()
+            .map(|&features| {
+                let linear = LinearConfig::new(in_features, features).init(device);
+                in_features = features;
+
+                MlpLayer {
+                    linear,
+                    batch_norm: match self.normalization {
+                        Some(Normalization::Batch) => {
+                            Some(BatchNormConfig::new(features).init(device))
+                        }
+                        _ => None,
+                    },
+                    layer_norm: match self.normalization {
+                        Some(Normalization::Layer) => {
+                            Some(LayerNormConfig::new(features).init(device))
+                        }
+                        _ => None,
+                    },
+                    activation: self.activation,
+                    dropout: self.dropout.map(|prob| DropoutConfig::new(prob).init()),
+                }
+            })
+            .collect();
+
+        Mlp {
+            layers,
+            head: LinearConfig::new(in_features, self.out_features).init(device),
+            output: self.output,
+        }
+    }
+}
+
+impl<B: Backend> Mlp<B> {
+    // Shapes
+    // - x: [batch_size, in_features]
+    // - y: [batch_size, out_features]
+    pub fn forward(&self, x: Tensor<B, 2>) -> Tensor<B, 2> {
+        let x = self.layers.iter().fold(x, |x, layer| layer.forward(x));
+        let x = self.head.forward(x);
+        match &self.output {
+            Some(output) => output.forward(x),
+            None => x,
+        }
+    }
+
+    pub fn forward_classification(
+        &self,
+        x: Tensor<B, 2>,
+        y: Tensor<B, 1, Int>,
+    ) -> ClassificationOutput<B> {
+        let output = self.forward(x);
+        let loss = CrossEntropyLoss::new(None, &output.device()).forward(output.clone(), y.clone());
+
+        ClassificationOutput::new(loss, output, y)
+    }
+
+    pub fn forward_regression(&self, x: Tensor<B, 2>, y: Tensor<B, 2>) -> RegressionOutput<B> {
+        let output = self.forward(x);
+        let loss = MseLoss::new().forward(output.clone(), y.clone(), Mean);
+
+        RegressionOutput::new(loss, output, y)
+    }
+}
+
+// Every classification dataset is batched into a `MoonsBatch` and every regression
+// dataset into a `ToyBatch`, so these are the only steps of the MLP
+impl<B: AutodiffBackend> TrainStep<MoonsBatch<B>, ClassificationOutput<B>> for Mlp<B> {
+    fn step(&self, batch: MoonsBatch<B>) -> TrainOutput<ClassificationOutput<B>> {
+        let item = self.forward_classification(batch.x, batch.y);
+
+        TrainOutput::new(self, item.loss.backward(), item)
+    }
+}
+
+impl<B: Backend> ValidStep<MoonsBatch<B>, ClassificationOutput<B>> for Mlp<B> {
+    fn step(&self, batch: MoonsBatch<B>) -> ClassificationOutput<B> {
+        self.forward_classification(batch.x, batch.y)
+    }
+}
+
+impl<B: AutodiffBackend> TrainStep<ToyBatch<B>, RegressionOutput<B>> for Mlp<B> {
+    fn step(&self, batch: ToyBatch<B>) -> TrainOutput<RegressionOutput<B>> {
+        let item = self.forward_regression(batch.x, batch.y);
+
+        TrainOutput::new(self, item.loss.backward(), item)
+    }
+}
+
+impl<B: Backend> ValidStep<ToyBatch<B>, RegressionOutput<B>> for Mlp<B> {
+    fn step(&self, batch: ToyBatch<B>) -> RegressionOutput<B> {
+        self.forward_regression(batch.x, batch.y)
+    }
+}
@@ -0,0 +1,3 @@
+pub mod cnn;
+pub mod layers;
+pub mod mlp;
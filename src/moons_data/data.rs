@@ -1,51 +1,107 @@
+use burn::config::Config;
 use burn::data::dataset::Dataset;
 use ndarray::{concatenate, stack, Array, Axis, Ix1, Ix2};
-use ndarray_rand::rand::seq::{IteratorRandom, SliceRandom};
-use ndarray_rand::rand::SeedableRng;
-use ndarray_rand::rand::{self, rngs};
+use ndarray_rand::rand::seq::SliceRandom;
+use ndarray_rand::rand::{self, SeedableRng};
 use ndarray_rand::rand_distr::{Distribution, Normal};
-use std::f32::consts::PI;
-
-pub fn make_moons(n_inner: usize, n_outer: usize, noise: f32) -> Vec<MoonsItem> {
-    let normal = Normal::<f32>::new(0.0, noise).unwrap();
-    let mut rng = rand::rngs::StdRng::seed_from_u64(21);
-
-    let inner_moon_x = Array::<f32, Ix1>::linspace(0.0, PI, n_inner)
-        .map(|x| 1.0 - x.cos() + normal.sample(&mut rng));
-    let inner_moon_y = Array::<f32, Ix1>::linspace(0.0, PI, n_inner)
-        .map(|x| 1.0 - x.sin() - 0.5 + normal.sample(&mut rng));
-
-    let inner_moon = stack(Axis(1), &[inner_moon_x.view(), inner_moon_y.view()]).unwrap();
-    let inner_class = Array::<i8, Ix1>::ones(n_inner);
-
-    let outer_moon_x =
-        Array::<f32, Ix1>::linspace(0.0, PI, n_outer).map(|x| x.cos() + normal.sample(&mut rng));
-    let outer_moon_y =
-        Array::<f32, Ix1>::linspace(0.0, PI, n_outer).map(|x| x.sin() + normal.sample(&mut rng));
-
-    let outer_moon: Array<f32, Ix2> =
-        stack(Axis(1), &[outer_moon_x.view(), outer_moon_y.view()]).unwrap();
-    let outer_class = Array::<i8, Ix1>::zeros(n_outer);
-
-    let x: Array<f32, Ix2> = concatenate(Axis(0), &[inner_moon.view(), outer_moon.view()]).unwrap();
-    let y: Array<i8, Ix1> =
-        concatenate(Axis(0), &[inner_class.view(), outer_class.view()]).unwrap();
-
-    let dataset = x
-        .axis_iter(Axis(0))
-        .zip(y.into_iter())
-        .map(|(x, y)| {
-            let x = match x.to_vec()[..] {
-                [x1, x2] => [x1, x2],
-                _ => {
-                    panic!("Expected two values");
-                }
-            };
-            MoonsItem { x, y }
-        })
-        .collect();
-
-    dataset
+use std::f64::consts::PI;
+
+use crate::split::{Split, SplitConfig};
+
+/// Two interleaving half circles with the semantics of `sklearn.datasets.make_moons`:
+/// the outer moon (class 0) is followed by the inner moon (class 1), the points are
+/// then optionally shuffled and finally gaussian noise is added to every coordinate.
+///
+/// Without noise and shuffling the output is numerically identical to scikit-learn.
+/// The random draws come from `StdRng`, so seeded outputs are reproducible but do not
+/// match the draws of NumPy's generator.
+#[derive(Config, Debug)]
+pub struct MoonsConfig {
+    pub n_samples_out: usize,
+    pub n_samples_in: usize,
+    pub noise: Option<f64>,
+    #[config(default = true)]
+    pub shuffle: bool,
+    #[config(default = 21)]
+    pub seed: u64,
+    #[config(default = "[0.0, 0.0]")]
+    pub offset: [f64; 2],
+    #[config(default = 1.0)]
+    pub scale: f64,
+}
+
+impl MoonsConfig {
+    /// Splits `n_samples` between both moons the same way scikit-learn does.
+    pub fn from_samples(n_samples: usize) -> Self {
+        let n_samples_out = n_samples / 2;
+        Self::new(n_samples_out, n_samples - n_samples_out)
+    }
+
+    /// Returns the points as a `[n_samples, 2]` array together with their classes.
+    pub fn arrays(&self) -> (Array<f64, Ix2>, Array<i8, Ix1>) {
+        let outer = linspace(self.n_samples_out);
+        let outer_moon_x = outer.mapv(f64::cos);
+        let outer_moon_y = outer.mapv(f64::sin);
+
+        let inner = linspace(self.n_samples_in);
+        let inner_moon_x = inner.mapv(|x| 1.0 - x.cos());
+        let inner_moon_y = inner.mapv(|x| 1.0 - x.sin() - 0.5);
+
+        let x1 = concatenate(Axis(0), &[outer_moon_x.view(), inner_moon_x.view()]).unwrap();
+        let x2 = concatenate(Axis(0), &[outer_moon_y.view(), inner_moon_y.view()]).unwrap();
+        let mut x: Array<f64, Ix2> = stack(Axis(1), &[x1.view(), x2.view()]).unwrap();
+        let mut y: Array<i8, Ix1> = concatenate(
+            Axis(0),
+            &[
+                Array::<i8, Ix1>::zeros(self.n_samples_out).view(),
+                Array::<i8, Ix1>::ones(self.n_samples_in).view(),
+            ],
+        )
+        .unwrap();
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(self.seed);
+
+        if self.shuffle {
+            let mut indices: Vec<usize> = (0..y.len()).collect();
+            indices.shuffle(&mut rng);
+            x = x.select(Axis(0), &indices);
+            y = y.select(Axis(0), &indices);
+        }
+
+        if let Some(noise) = self.noise {
+            let normal = Normal::<f64>::new(0.0, noise).unwrap();
+            x.iter_mut()
+                .for_each(|value| *value += normal.sample(&mut rng));
+        }
+
+        let offset = Array::from_vec(self.offset.to_vec());
+        let x = x * self.scale + offset;
+
+        (x, y)
+    }
+
+    pub fn generate(&self) -> Vec<MoonsItem> {
+        let (x, y) = self.arrays();
+
+        x.axis_iter(Axis(0))
+            .zip(y)
+            .map(|(x, y)| MoonsItem {
+                x: [x[0] as f32, x[1] as f32],
+                y,
+            })
+            .collect()
+    }
+}
+
+// Evenly spaced angles in [0, PI] computed like `numpy.linspace`, which pins the
+// last value to PI instead of accumulating the step
+fn linspace(n: usize) -> Array<f64, Ix1> {
+    let step = PI / n.saturating_sub(1).max(1) as f64;
+    let mut angles = Array::from_iter((0..n).map(|i| i as f64 * step));
+    if n > 1 {
+        angles[n - 1] = PI;
+    }
+    angles
 }
 
 #[derive(Clone, Debug)]
@@ -68,39 +124,31 @@ impl Dataset<MoonsItem> for MoonsDataset {
     }
 }
 
+#[derive(Config, Debug)]
 pub struct MoonDatasetConfig {
-    pub n_inner: usize,
-    pub n_outer: usize,
-    pub split: f32,
-    pub noise: f32,
+    pub moons: MoonsConfig,
+    pub split: SplitConfig,
 }
 
 impl MoonDatasetConfig {
     pub fn train(&self) -> MoonsDataset {
-        self.new("train")
+        self.dataset(Split::Train)
+    }
+
+    pub fn valid(&self) -> MoonsDataset {
+        self.dataset(Split::Valid)
     }
 
     pub fn test(&self) -> MoonsDataset {
-        self.new("test")
+        self.dataset(Split::Test)
     }
 
-    fn new(&self, split: &str) -> MoonsDataset {
-        let mut rng = rngs::StdRng::seed_from_u64(12);
-        let mut items = make_moons(self.n_inner, self.n_outer, self.noise);
-        items.shuffle(&mut rng);
-        let amount = (self.split * (self.n_inner + self.n_outer) as f32).ceil() as usize;
-
-        let split_items = match split {
-            "train" => items.into_iter().choose_multiple(&mut rng, amount),
-            _ => items
-                .into_iter()
-                .choose_multiple(&mut rng, self.n_inner + self.n_outer)
-                .drain(0..amount)
-                .collect(),
-        };
+    fn dataset(&self, split: Split) -> MoonsDataset {
+        let items = self.moons.generate();
+        let splits = self.split.split_by(items, |item| item.y);
 
         MoonsDataset {
-            dataset: split_items,
+            dataset: splits.take(split),
         }
     }
 }
@@ -0,0 +1,148 @@
+use std::fmt::Display;
+
+use burn::{
+    config::Config,
+    module::AutodiffModule,
+    optim::{AdamConfig, AdamWConfig, GradientsParams, Optimizer, RmsPropConfig, SgdConfig},
+    tensor::backend::AutodiffBackend,
+    LearningRate,
+};
+
+/// Optimizer of the training. Each config has its own weight decay and gradient
+/// clipping options.
+#[derive(Config)]
+pub enum OptimizerConfig {
+    /// Stochastic gradient descent, with (Nesterov) momentum through its `momentum`.
+    Sgd(SgdConfig),
+    Adam(AdamConfig),
+    /// Adam with decoupled weight decay.
+    AdamW(AdamWConfig),
+    RmsProp(RmsPropConfig),
+}
+
+#[derive(Debug)]
+pub enum OptimizerError {
+    /// The checkpoint was saved by another optimizer, whose state cannot be loaded.
+    Mismatch {
+        saved: &'static str,
+        configured: &'static str,
+    },
+}
+
+impl Display for OptimizerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OptimizerError::Mismatch { saved, configured } => write!(
+                f,
+                "The checkpoint was saved by the {saved} optimizer and cannot be resumed with {configured}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for OptimizerError {}
+
+impl OptimizerConfig {
+    pub fn name(&self) -> &'static str {
+        match self {
+            OptimizerConfig::Sgd(_) => "Sgd",
+            OptimizerConfig::Adam(_) => "Adam",
+            OptimizerConfig::AdamW(_) => "AdamW",
+            OptimizerConfig::RmsProp(_) => "RmsProp",
+        }
+    }
+
+    /// Checks that a checkpoint saved with the `saved` optimizer can be resumed with
+    /// this one, which only loads the state of its own kind of optimizer.
+    pub fn check_resume(&self, saved: &OptimizerConfig) -> Result<(), OptimizerError> {
+        match self.name() == saved.name() {
+            true => Ok(()),
+            false => Err(OptimizerError::Mismatch {
+                saved: saved.name(),
+                configured: self.name(),
+            }),
+        }
+    }
+
+    pub fn init<B: AutodiffBackend, M: AutodiffModule<B>>(
+        &self,
+    ) -> ConfiguredOptimizer<
+        impl Optimizer<M, B>,
+        impl Optimizer<M, B>,
+        impl Optimizer<M, B>,
+        impl Optimizer<M, B>,
+    > {
+        match self {
+            OptimizerConfig::Sgd(config) => ConfiguredOptimizer::Sgd(config.init()),
+            OptimizerConfig::Adam(config) => ConfiguredOptimizer::Adam(config.init()),
+            OptimizerConfig::AdamW(config) => ConfiguredOptimizer::AdamW(config.init()),
+            OptimizerConfig::RmsProp(config) => ConfiguredOptimizer::RmsProp(config.init()),
+        }
+    }
+}
+
+/// Any of the optimizers of [`OptimizerConfig`], so that the learner has a single
+/// optimizer type whatever the config. The types are those returned by the `init`
+/// of each config, which burn doesn't always name.
+pub enum ConfiguredOptimizer<S, A, W, R> {
+    Sgd(S),
+    Adam(A),
+    AdamW(W),
+    RmsProp(R),
+}
+
+impl<M, B, S, A, W, R> Optimizer<M, B> for ConfiguredOptimizer<S, A, W, R>
+where
+    M: AutodiffModule<B>,
+    B: AutodiffBackend,
+    S: Optimizer<M, B>,
+    A: Optimizer<M, B>,
+    W: Optimizer<M, B>,
+    R: Optimizer<M, B>,
+{
+    /// State of the optimizer in use, the others are `None`.
+    type Record = (
+        Option<S::Record>,
+        Option<A::Record>,
+        Option<W::Record>,
+        Option<R::Record>,
+    );
+
+    fn step(&mut self, lr: LearningRate, module: M, grads: GradientsParams) -> M {
+        match self {
+            ConfiguredOptimizer::Sgd(optim) => optim.step(lr, module, grads),
+            ConfiguredOptimizer::Adam(optim) => optim.step(lr, module, grads),
+            ConfiguredOptimizer::AdamW(optim) => optim.step(lr, module, grads),
+            ConfiguredOptimizer::RmsProp(optim) => optim.step(lr, module, grads),
+        }
+    }
+
+    fn to_record(&self) -> Self::Record {
+        match self {
+            ConfiguredOptimizer::Sgd(optim) => (Some(optim.to_record()), None, None, None),
+            ConfiguredOptimizer::Adam(optim) => (None, Some(optim.to_record()), None, None),
+            ConfiguredOptimizer::AdamW(optim) => (None, None, Some(optim.to_record()), None),
+            ConfiguredOptimizer::RmsProp(optim) => (None, None, None, Some(optim.to_record())),
+        }
+    }
+
+    fn load_record(self, (sgd, adam, adamw, rmsprop): Self::Record) -> Self {
+        // Resuming checks the optimizer first, see `OptimizerConfig::check_resume`
+        const MESSAGE: &str = "Checkpoint should be saved by the configured optimizer";
+
+        match self {
+            ConfiguredOptimizer::Sgd(optim) => {
+                ConfiguredOptimizer::Sgd(optim.load_record(sgd.expect(MESSAGE)))
+            }
+            ConfiguredOptimizer::Adam(optim) => {
+                ConfiguredOptimizer::Adam(optim.load_record(adam.expect(MESSAGE)))
+            }
+            ConfiguredOptimizer::AdamW(optim) => {
+                ConfiguredOptimizer::AdamW(optim.load_record(adamw.expect(MESSAGE)))
+            }
+            ConfiguredOptimizer::RmsProp(optim) => {
+                ConfiguredOptimizer::RmsProp(optim.load_record(rmsprop.expect(MESSAGE)))
+            }
+        }
+    }
+}
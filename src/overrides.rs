@@ -0,0 +1,181 @@
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+
+use burn::config::Config;
+use serde_json::Value;
+
+#[derive(Debug)]
+pub enum OverrideError {
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    InvalidFile {
+        path: PathBuf,
+        message: String,
+    },
+    UnknownKey {
+        key: String,
+    },
+    InvalidValue {
+        key: String,
+        message: String,
+    },
+    /// The overrides are invalid together but none of them is on its own.
+    Invalid {
+        message: String,
+    },
+}
+
+impl Display for OverrideError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OverrideError::Io { path, source } => {
+                write!(f, "Could not read {}: {source}", path.display())
+            }
+            OverrideError::InvalidFile { path, message } => {
+                write!(f, "Config {} is invalid: {message}", path.display())
+            }
+            OverrideError::UnknownKey { key } => write!(f, "Unknown config key {key}"),
+            OverrideError::InvalidValue { key, message } => {
+                write!(f, "Invalid value for {key}: {message}")
+            }
+            OverrideError::Invalid { message } => write!(f, "Invalid config: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for OverrideError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            OverrideError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Replaces the fields of `config` found in a JSON file. The file may be partial,
+/// e.g. `{"training": {"num_epochs": 5}}` only changes the number of epochs.
+pub fn merge_file<C: Config>(config: &C, path: impl AsRef<Path>) -> Result<C, OverrideError> {
+    let path = path.as_ref();
+    let contents = std::fs::read_to_string(path).map_err(|source| OverrideError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let file: Value =
+        serde_json::from_str(&contents).map_err(|err| OverrideError::InvalidFile {
+            path: path.to_path_buf(),
+            message: err.to_string(),
+        })?;
+
+    let mut value = to_value(config);
+    merge(&mut value, file.clone());
+
+    let merged = serde_json::from_value(value).map_err(|err| OverrideError::InvalidFile {
+        path: path.to_path_buf(),
+        message: err.to_string(),
+    })?;
+
+    // Deserializing skips the fields it does not know, so look for the keys of the
+    // file that did not make it into the config, as `--set` would reject them
+    match unknown_key(&to_value(&merged), &file, "") {
+        Some(key) => Err(OverrideError::UnknownKey { key }),
+        None => Ok(merged),
+    }
+}
+
+/// Sets each `(key, value)` pair on `config`, where the key is a dotted path such as
+/// `training.learning_rate`. Values are parsed as JSON, falling back to a string.
+pub fn apply<C: Config>(config: &C, overrides: &[(String, String)]) -> Result<C, OverrideError> {
+    let mut value = to_value(config);
+
+    for (key, raw) in overrides {
+        set(&mut value, key, parse(raw))?;
+    }
+
+    serde_json::from_value(value).map_err(|err| {
+        let message = err.to_string();
+
+        // The error does not say which field failed, blame the first override that
+        // is invalid on its own
+        let invalid = overrides.iter().find(|(key, raw)| {
+            let mut value = to_value(config);
+            set(&mut value, key, parse(raw)).is_ok() && serde_json::from_value::<C>(value).is_err()
+        });
+        match invalid {
+            Some((key, _)) => OverrideError::InvalidValue {
+                key: key.clone(),
+                message,
+            },
+            None => OverrideError::Invalid { message },
+        }
+    })
+}
+
+// Values are JSON, or strings when they are not valid JSON
+fn parse(raw: &str) -> Value {
+    serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_string()))
+}
+
+/// Replaces the existing field at the dotted path `key` of a serialized config.
+pub fn set(value: &mut Value, key: &str, new: Value) -> Result<(), OverrideError> {
+    let unknown = || OverrideError::UnknownKey {
+        key: key.to_string(),
+    };
+
+    let field = key.split('.').try_fold(value, |value, name| {
+        value
+            .as_object_mut()
+            .and_then(|object| object.get_mut(name))
+    });
+
+    *field.ok_or_else(unknown)? = new;
+    Ok(())
+}
+
+/// Reads the field at the dotted path `key` of a serialized config.
+pub fn get<'a>(value: &'a Value, key: &str) -> Option<&'a Value> {
+    key.split('.')
+        .try_fold(value, |value, name| value.as_object()?.get(name))
+}
+
+pub fn to_value<C: Config>(config: &C) -> Value {
+    serde_json::to_value(config).expect("Config should be serialized successfully")
+}
+
+fn merge(value: &mut Value, other: Value) {
+    match (value, other) {
+        // Another variant of an enum, e.g. `{"Sgd": {..}}` replacing `{"Adam": {..}}`
+        (Value::Object(object), Value::Object(other))
+            if object.len() == 1 && other.len() == 1 && object.keys().ne(other.keys()) =>
+        {
+            *object = other;
+        }
+        (Value::Object(object), Value::Object(other)) => {
+            for (key, other) in other {
+                match object.get_mut(&key) {
+                    Some(value) => merge(value, other),
+                    None => {
+                        object.insert(key, other);
+                    }
+                }
+            }
+        }
+        (value, other) => *value = other,
+    }
+}
+
+// Dotted path of the first key of `file` missing from the config `value`
+fn unknown_key(value: &Value, file: &Value, prefix: &str) -> Option<String> {
+    let (Value::Object(object), Value::Object(file)) = (value, file) else {
+        return None;
+    };
+
+    file.iter().find_map(|(name, file)| {
+        let key = format!("{prefix}{name}");
+        match object.get(name) {
+            Some(value) => unknown_key(value, file, &format!("{key}.")),
+            None => Some(key),
+        }
+    })
+}
@@ -0,0 +1,76 @@
+use std::collections::BTreeSet;
+
+use plotly::{
+    common::Mode,
+    layout::{GridPattern, LayoutGrid},
+    Layout, Plot, Scatter,
+};
+
+use crate::history::MetricHistory;
+
+/// Curves of metric histories, each trace being named by the caller, with one subplot
+/// per metric in alphabetical order.
+pub(crate) fn curves<'a>(traces: impl IntoIterator<Item = (String, &'a MetricHistory)>) -> Plot {
+    let traces: Vec<(String, &MetricHistory)> = traces.into_iter().collect();
+    let names: BTreeSet<&str> = traces
+        .iter()
+        .map(|(_, history)| history.metric.as_str())
+        .collect();
+    let names: Vec<&str> = names.into_iter().collect();
+    let mut plot = Plot::new();
+
+    for (name, history) in &traces {
+        let row = names
+            .iter()
+            .position(|metric| *metric == history.metric)
+            .unwrap()
+            + 1;
+        let axis = |axis: &str| match row {
+            1 => axis.to_string(),
+            _ => format!("{axis}{row}"),
+        };
+
+        let (epochs, values): (Vec<usize>, Vec<f64>) = history
+            .epochs
+            .iter()
+            .map(|value| (value.epoch, value.value))
+            .unzip();
+
+        plot.add_trace(
+            Scatter::new(epochs, values)
+                .name(name)
+                .mode(Mode::LinesMarkers)
+                .x_axis(axis("x"))
+                .y_axis(axis("y")),
+        );
+    }
+
+    plot.set_layout(
+        Layout::new().grid(
+            LayoutGrid::new()
+                .rows(names.len().max(1))
+                .columns(1)
+                .pattern(GridPattern::Independent),
+        ),
+    );
+
+    plot
+}
+
+/// Writes CSV rows without quoted fields as a table with aligned columns.
+pub(crate) fn write_table(f: &mut std::fmt::Formatter<'_>, csv: &str) -> std::fmt::Result {
+    let rows: Vec<Vec<&str>> = csv.lines().map(|line| line.split(',').collect()).collect();
+    let widths: Vec<usize> = (0..rows.first().map_or(0, Vec::len))
+        .map(|i| rows.iter().map(|row| row[i].len()).max().unwrap_or(0))
+        .collect();
+
+    for row in rows {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &width)| format!("{cell:<width$}"))
+            .collect();
+        writeln!(f, "{}", cells.join("  ").trim_end())?;
+    }
+    Ok(())
+}
@@ -0,0 +1,419 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use burn::config::{Config, ConfigError};
+use plotly::Plot;
+
+use crate::history::TrainingHistory;
+use crate::report;
+use crate::train::{ArtifactConfig, ArtifactMetadata};
+
+const MANIFEST_FILE: &str = "manifest.json";
+
+#[derive(Debug)]
+pub enum RunError {
+    NoRuns {
+        dir: PathBuf,
+    },
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+}
+
+impl Display for RunError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RunError::NoRuns { dir } => write!(f, "No runs were found in {}", dir.display()),
+            RunError::Io { path, source } => {
+                write!(f, "Could not access {}: {source}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for RunError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RunError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Everything needed to compare a training run with the others, saved as
+/// `manifest.json` in its run directory.
+#[derive(Config, Debug)]
+pub struct RunManifest {
+    /// Name of the run directory.
+    pub id: String,
+    /// Start of the run in UTC, e.g. `2024-05-17T09:30:00Z`.
+    pub started_at: String,
+    /// Commit of the sources, with a `-dirty` suffix for uncommitted changes.
+    pub git_revision: Option<String>,
+    pub seed: u64,
+    pub backend: String,
+    /// Hash of the description of the data, of the size of its splits and of the size
+    /// and modification time of the files it is read from, see [`dataset_fingerprint`].
+    pub dataset_fingerprint: String,
+    /// Epoch of the saved model.
+    pub epoch: Option<usize>,
+    /// Value of each metric at that epoch, keyed by `split/metric`, e.g. `valid/Loss`.
+    pub metrics: BTreeMap<String, f64>,
+    /// Wall-clock time of the training in seconds, summed over its resumptions.
+    pub duration_secs: f64,
+}
+
+impl RunManifest {
+    pub fn save_to(&self, run_dir: &str) -> std::io::Result<()> {
+        self.save(format!("{run_dir}/{MANIFEST_FILE}"))
+    }
+
+    pub fn load_from(run_dir: &str) -> Result<Self, ConfigError> {
+        Self::load(format!("{run_dir}/{MANIFEST_FILE}"))
+    }
+}
+
+/// Run being trained, which keeps its manifest up to date.
+pub struct Run {
+    manifest: RunManifest,
+    clock: Instant,
+}
+
+impl Run {
+    /// Saves the manifest of a new run. When the run is resumed, its start, time and
+    /// metrics so far are kept, but the seed, backend, revision and data are the ones
+    /// the resumed training uses.
+    pub fn start<C: ArtifactConfig>(
+        run_dir: &str,
+        config: &C,
+        backend: String,
+        num_items: [usize; 2],
+    ) -> Self {
+        let current = RunManifest {
+            id: file_name(Path::new(run_dir)),
+            started_at: {
+                let [year, month, day, hours, minutes, seconds] = utc(SystemTime::now());
+                format!("{year:04}-{month:02}-{day:02}T{hours:02}:{minutes:02}:{seconds:02}Z")
+            },
+            git_revision: git_revision(),
+            seed: config.training().seed,
+            backend,
+            dataset_fingerprint: dataset_fingerprint(
+                &config.dataset(),
+                num_items,
+                &config.data_files(),
+            ),
+            epoch: None,
+            metrics: BTreeMap::new(),
+            duration_secs: 0.0,
+        };
+        let manifest = match RunManifest::load_from(run_dir) {
+            Ok(resumed) => RunManifest {
+                id: resumed.id,
+                started_at: resumed.started_at,
+                epoch: resumed.epoch,
+                metrics: resumed.metrics,
+                duration_secs: resumed.duration_secs,
+                ..current
+            },
+            Err(_) => current,
+        };
+
+        manifest
+            .save_to(run_dir)
+            .expect("Run manifest should be saved successfully");
+
+        Self {
+            manifest,
+            clock: Instant::now(),
+        }
+    }
+
+    /// Records the metrics of the saved model and the time spent training.
+    pub fn finish(mut self, run_dir: &str, history: &TrainingHistory, metadata: &ArtifactMetadata) {
+        self.manifest.epoch = Some(metadata.epoch);
+        self.manifest.metrics = history
+            .metrics
+            .iter()
+            .filter_map(|metric| {
+                let value = metric.epochs.iter().find(|v| v.epoch == metadata.epoch)?;
+                Some((format!("{}/{}", metric.split, metric.metric), value.value))
+            })
+            .collect();
+        self.manifest.duration_secs += self.clock.elapsed().as_secs_f64();
+
+        self.manifest
+            .save_to(run_dir)
+            .expect("Run manifest should be saved successfully");
+    }
+}
+
+/// Fingerprint telling whether two runs trained on the same data. The files are only
+/// compared by size and modification time, so that large datasets are not read again,
+/// and data generated from a config is covered by its description.
+pub fn dataset_fingerprint(description: &str, num_items: [usize; 2], files: &[PathBuf]) -> String {
+    let mut data = format!("{description} {num_items:?}");
+    for path in files {
+        let metadata = std::fs::metadata(path).ok();
+        let size = metadata.as_ref().map(|metadata| metadata.len());
+        let modified = metadata
+            .and_then(|metadata| metadata.modified().ok())
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|time| time.as_nanos());
+        data.push_str(&format!("\n{} {size:?} {modified:?}", path.display()));
+    }
+
+    format!("{:016x}", fnv1a(data.as_bytes()))
+}
+
+/// Files found in `dir` and its subdirectories, sorted by path.
+pub fn data_files(dir: impl AsRef<Path>) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for path in entries(dir.as_ref()).unwrap_or_default() {
+        match path.is_dir() {
+            true => files.extend(data_files(&path)),
+            false => files.push(path),
+        }
+    }
+    files
+}
+
+/// Creates the directory of a new run in `runs_dir`, named after the current time and
+/// the config. Runs of the same config started within the same second get a `-2`,
+/// `-3`, ... suffix rather than the directory of the first one.
+pub fn new_run_dir<C: Config>(runs_dir: &str, config: &C) -> Result<String, RunError> {
+    let json = serde_json::to_string(config).expect("Config should be serialized successfully");
+    let hash = fnv1a(json.as_bytes());
+    let name = format!(
+        "{}/{}-{:08x}",
+        runs_dir.trim_end_matches('/'),
+        timestamp(),
+        hash as u32
+    );
+    let io_error = |path: &str| {
+        let path = PathBuf::from(path);
+        move |source| RunError::Io { path, source }
+    };
+
+    std::fs::create_dir_all(runs_dir).map_err(io_error(runs_dir))?;
+    // Creating the directory fails when it exists, so concurrent runs cannot share it
+    for n in 1.. {
+        let run_dir = match n {
+            1 => name.clone(),
+            n => format!("{name}-{n}"),
+        };
+        match std::fs::create_dir(&run_dir) {
+            Ok(()) => return Ok(run_dir),
+            Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(err) => return Err(io_error(&run_dir)(err)),
+        }
+    }
+    unreachable!("A run directory should be free")
+}
+
+/// Current time in UTC as `YYYYMMDD-HHMMSS`, which sorts chronologically.
+pub fn timestamp() -> String {
+    let [year, month, day, hours, minutes, seconds] = utc(SystemTime::now());
+    format!("{year:04}{month:02}{day:02}-{hours:02}{minutes:02}{seconds:02}")
+}
+
+/// Directory of the most recent run in `runs_dir`.
+pub fn latest_run_dir(runs_dir: &str) -> Result<String, RunError> {
+    run_dirs(runs_dir)?
+        .pop()
+        .map(|dir| dir.to_string_lossy().into_owned())
+        .ok_or_else(|| RunError::NoRuns {
+            dir: PathBuf::from(runs_dir),
+        })
+}
+
+/// Manifests and learning curves of every run of a chapter, including those of its
+/// sweeps, oldest first.
+pub struct RunReport {
+    pub runs: Vec<(RunManifest, Option<TrainingHistory>)>,
+}
+
+impl RunReport {
+    /// Reads the runs of `runs_dir` and of its `sweep-*` directories that have a
+    /// manifest. Sweep runs are named `sweep-<time>/<run>`, which `--run` accepts.
+    pub fn load(runs_dir: &str) -> Result<Self, RunError> {
+        let mut dirs = run_dirs(runs_dir)?;
+        for sweep_dir in entries(Path::new(runs_dir))? {
+            if file_name(&sweep_dir).starts_with("sweep-") && sweep_dir.is_dir() {
+                dirs.extend(run_dirs(&sweep_dir)?);
+            }
+        }
+        dirs.sort_by_key(|dir| dir.file_name().map(ToOwned::to_owned));
+
+        let runs: Vec<_> = dirs
+            .into_iter()
+            .filter_map(|dir| {
+                let mut manifest = RunManifest::load_from(dir.to_str()?).ok()?;
+                manifest.id = dir
+                    .strip_prefix(runs_dir)
+                    .ok()?
+                    .to_string_lossy()
+                    .into_owned();
+                Some((manifest, TrainingHistory::load(&dir).ok()))
+            })
+            .collect();
+
+        if runs.is_empty() {
+            return Err(RunError::NoRuns {
+                dir: PathBuf::from(runs_dir),
+            });
+        }
+
+        Ok(Self { runs })
+    }
+
+    /// Names of the metrics of every run, in alphabetical order.
+    pub fn metric_names(&self) -> Vec<&str> {
+        let names: BTreeSet<&str> = self
+            .runs
+            .iter()
+            .flat_map(|(manifest, _)| manifest.metrics.keys().map(String::as_str))
+            .collect();
+        names.into_iter().collect()
+    }
+
+    /// One row per run with its manifest and metrics, with a header.
+    pub fn to_csv(&self) -> String {
+        let metrics = self.metric_names();
+        let mut csv = String::from("run,git_revision,seed,backend,dataset,epoch,duration_secs");
+        for metric in &metrics {
+            csv.push_str(&format!(",{metric}"));
+        }
+        csv.push('\n');
+
+        for (manifest, _) in &self.runs {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{:.1}",
+                manifest.id,
+                manifest.git_revision.as_deref().unwrap_or_default(),
+                manifest.seed,
+                manifest.backend,
+                manifest.dataset_fingerprint,
+                manifest.epoch.map(|e| e.to_string()).unwrap_or_default(),
+                manifest.duration_secs,
+            ));
+            for metric in &metrics {
+                let value = manifest.metrics.get(*metric);
+                csv.push_str(&format!(
+                    ",{}",
+                    value.map(f64::to_string).unwrap_or_default()
+                ));
+            }
+            csv.push('\n');
+        }
+        csv
+    }
+
+    /// Validation curves with one subplot per metric and one trace per run.
+    pub fn plot(&self) -> Plot {
+        report::curves(self.runs.iter().flat_map(|(manifest, history)| {
+            history
+                .iter()
+                .flat_map(|history| &history.metrics)
+                .filter(|metric| metric.split == "valid")
+                .map(|metric| (format!("{} ({})", metric.metric, manifest.id), metric))
+        }))
+    }
+
+    /// Writes `report.html` and `report.csv` in `dir`.
+    pub fn write(&self, dir: impl AsRef<Path>) -> Result<(), RunError> {
+        let dir = dir.as_ref();
+
+        let mut plot = self.plot();
+        plot.use_local_plotly();
+        plot.write_html(dir.join("report.html"));
+
+        let path = dir.join("report.csv");
+        std::fs::write(&path, self.to_csv()).map_err(|source| RunError::Io { path, source })
+    }
+}
+
+// Same rows as the CSV, with aligned columns
+impl Display for RunReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        report::write_table(f, &self.to_csv())
+    }
+}
+
+// Run directories sorted by name, so by start time
+fn run_dirs(runs_dir: impl AsRef<Path>) -> Result<Vec<PathBuf>, RunError> {
+    let mut dirs = entries(runs_dir.as_ref())?;
+    dirs.retain(|dir| dir.join("config.json").is_file());
+
+    Ok(dirs)
+}
+
+// Paths of the entries of a directory, sorted
+fn entries(dir: &Path) -> Result<Vec<PathBuf>, RunError> {
+    let io_error = |source| RunError::Io {
+        path: dir.to_path_buf(),
+        source,
+    };
+
+    let mut paths = std::fs::read_dir(dir)
+        .map_err(io_error)?
+        .map(|entry| entry.map(|entry| entry.path()).map_err(io_error))
+        .collect::<Result<Vec<_>, _>>()?;
+    paths.sort();
+
+    Ok(paths)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+fn git_revision() -> Option<String> {
+    let git = |args: &[&str]| {
+        let output = Command::new("git").args(args).output().ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    let revision = git(&["rev-parse", "--short=12", "HEAD"])?;
+    match git(&["status", "--porcelain", "--untracked-files=no"]) {
+        Some(changes) if !changes.is_empty() => Some(format!("{revision}-dirty")),
+        _ => Some(revision),
+    }
+}
+
+// Year, month, day, hours, minutes and seconds in UTC
+fn utc(time: SystemTime) -> [u64; 6] {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let (days, secs) = (secs / 86_400, secs % 86_400);
+
+    // Civil date from the days since 1970-01-01, after Howard Hinnant's algorithm
+    let z = days + 719_468;
+    let (era, doe) = (z / 146_097, z % 146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    [year, month, day, secs / 3_600, secs / 60 % 60, secs % 60]
+}
+
+// FNV-1a, stable across Rust versions unlike the std hashers
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
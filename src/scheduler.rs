@@ -0,0 +1,197 @@
+use std::f64::consts::PI;
+
+use burn::{config::Config, lr_scheduler::LrScheduler, tensor::backend::Backend, LearningRate};
+
+/// Schedule of the learning rate over the epochs, relative to the learning rate of
+/// the [`TrainingConfig`](crate::train::TrainingConfig).
+#[derive(Config, Debug, Copy, PartialEq)]
+pub enum SchedulerConfig {
+    Constant,
+    /// Multiplies the learning rate by `gamma` every `step_size` epochs.
+    StepDecay {
+        step_size: usize,
+        gamma: f64,
+    },
+    /// Multiplies the learning rate by `gamma` every epoch.
+    Exponential {
+        gamma: f64,
+    },
+    /// Anneals the learning rate down to `min_lr` along a cosine over `period`
+    /// epochs, then restarts with a period multiplied by `period_mult`.
+    CosineWarmRestarts {
+        period: usize,
+        period_mult: usize,
+        min_lr: f64,
+    },
+    /// Increases the learning rate linearly from zero over `warmup_epochs`, then
+    /// keeps it constant.
+    LinearWarmup {
+        warmup_epochs: usize,
+    },
+    /// Anneals the learning rate up from `learning_rate / div_factor` over the first
+    /// `pct_start` of the training, then down to
+    /// `learning_rate / (div_factor * final_div_factor)` at its last iteration.
+    OneCycle {
+        pct_start: f64,
+        div_factor: f64,
+        final_div_factor: f64,
+    },
+}
+
+impl SchedulerConfig {
+    /// Returns the scheduler stepped once per training iteration, with
+    /// `iterations_per_epoch` batches in each of the `num_epochs` epochs. Panics on
+    /// parameters giving a zero, negative or undefined learning rate.
+    pub fn init(
+        &self,
+        learning_rate: f64,
+        num_epochs: usize,
+        iterations_per_epoch: usize,
+    ) -> Scheduler {
+        self.check();
+
+        Scheduler {
+            config: *self,
+            learning_rate,
+            num_epochs,
+            iterations_per_epoch: iterations_per_epoch.max(1),
+            iteration: 0,
+        }
+    }
+
+    fn check(&self) {
+        let positive = |name: &str, value: f64| {
+            assert!(
+                value.is_finite() && value > 0.0,
+                "The {name} of the scheduler should be positive, got {value}"
+            )
+        };
+        let nonzero = |name: &str, value: usize| {
+            assert!(
+                value > 0,
+                "The {name} of the scheduler should be at least 1"
+            )
+        };
+
+        match *self {
+            SchedulerConfig::Constant | SchedulerConfig::LinearWarmup { .. } => {}
+            SchedulerConfig::StepDecay { step_size, gamma } => {
+                nonzero("step_size", step_size);
+                positive("gamma", gamma);
+            }
+            SchedulerConfig::Exponential { gamma } => positive("gamma", gamma),
+            SchedulerConfig::CosineWarmRestarts {
+                period,
+                period_mult,
+                min_lr,
+            } => {
+                nonzero("period", period);
+                nonzero("period_mult", period_mult);
+                assert!(
+                    min_lr.is_finite() && min_lr >= 0.0,
+                    "The min_lr of the scheduler should not be negative, got {min_lr}"
+                );
+            }
+            SchedulerConfig::OneCycle {
+                pct_start,
+                div_factor,
+                final_div_factor,
+            } => {
+                // Both phases need some iterations, or the schedule divides by zero
+                assert!(
+                    pct_start > 0.0 && pct_start < 1.0,
+                    "The pct_start of the scheduler should be between 0 and 1 exclusive, got {pct_start}"
+                );
+                positive("div_factor", div_factor);
+                positive("final_div_factor", final_div_factor);
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Scheduler {
+    config: SchedulerConfig,
+    learning_rate: f64,
+    num_epochs: usize,
+    iterations_per_epoch: usize,
+    iteration: usize,
+}
+
+impl Scheduler {
+    /// Learning rate of the given training iteration, counted from 0.
+    pub fn learning_rate_at(&self, iteration: usize) -> LearningRate {
+        let lr = self.learning_rate;
+        let epoch = iteration / self.iterations_per_epoch;
+        // Fractional number of epochs done, for the schedules that change every iteration
+        let progress = iteration as f64 / self.iterations_per_epoch as f64;
+
+        match self.config {
+            SchedulerConfig::Constant => lr,
+            SchedulerConfig::StepDecay { step_size, gamma } => {
+                lr * gamma.powi((epoch / step_size) as i32)
+            }
+            SchedulerConfig::Exponential { gamma } => lr * gamma.powi(epoch as i32),
+            SchedulerConfig::CosineWarmRestarts {
+                period,
+                period_mult,
+                min_lr,
+            } => {
+                let mut period = period as f64;
+                let mut progress = progress;
+                while progress >= period {
+                    progress -= period;
+                    period *= period_mult as f64;
+                }
+                cosine(lr, min_lr, progress / period)
+            }
+            SchedulerConfig::LinearWarmup { warmup_epochs } => {
+                let warmup = (warmup_epochs * self.iterations_per_epoch) as f64;
+                lr * ((iteration + 1) as f64 / warmup).min(1.0)
+            }
+            SchedulerConfig::OneCycle {
+                pct_start,
+                div_factor,
+                final_div_factor,
+            } => {
+                // Counted so that the last iteration reaches the final learning rate
+                let last = (self.num_epochs * self.iterations_per_epoch).max(2) - 1;
+                let progress = iteration as f64 / last as f64;
+                let initial_lr = lr / div_factor;
+                match progress < pct_start {
+                    true => cosine(initial_lr, lr, progress / pct_start),
+                    false => cosine(
+                        lr,
+                        initial_lr / final_div_factor,
+                        ((progress - pct_start) / (1.0 - pct_start)).min(1.0),
+                    ),
+                }
+            }
+        }
+    }
+}
+
+impl<B: Backend> LrScheduler<B> for Scheduler {
+    // Only the iteration changes during the training, the rest comes from the config
+    type Record = usize;
+
+    fn step(&mut self) -> LearningRate {
+        let lr = self.learning_rate_at(self.iteration);
+        self.iteration += 1;
+        lr
+    }
+
+    fn to_record(&self) -> Self::Record {
+        self.iteration
+    }
+
+    fn load_record(mut self, record: Self::Record) -> Self {
+        self.iteration = record;
+        self
+    }
+}
+
+// Goes from `start` to `end` along half a cosine as `t` goes from 0 to 1
+fn cosine(start: f64, end: f64, t: f64) -> f64 {
+    end + (start - end) * (1.0 + (PI * t).cos()) / 2.0
+}
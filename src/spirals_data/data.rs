@@ -0,0 +1,110 @@
+use burn::config::Config;
+use burn::data::dataset::Dataset;
+use ndarray_rand::rand::seq::SliceRandom;
+use ndarray_rand::rand::{self, SeedableRng};
+use ndarray_rand::rand_distr::{Distribution, Normal};
+use std::f64::consts::PI;
+
+use crate::split::{Split, SplitConfig};
+
+/// `n_arms` interleaved spirals growing from the origin, each arm being one class.
+#[derive(Config, Debug)]
+pub struct SpiralsConfig {
+    pub n_samples_per_arm: usize,
+    pub n_arms: usize,
+    pub noise: Option<f64>,
+    /// Number of revolutions of every arm.
+    #[config(default = 1.0)]
+    pub turns: f64,
+    #[config(default = true)]
+    pub shuffle: bool,
+    #[config(default = 21)]
+    pub seed: u64,
+}
+
+impl SpiralsConfig {
+    pub fn generate(&self) -> Vec<SpiralsItem> {
+        assert!(
+            self.n_arms <= i8::MAX as usize,
+            "At most {} arms are supported",
+            i8::MAX
+        );
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(self.seed);
+        let normal = self
+            .noise
+            .map(|noise| Normal::<f64>::new(0.0, noise).unwrap());
+        let step = 1.0 / self.n_samples_per_arm.saturating_sub(1).max(1) as f64;
+
+        let mut items: Vec<SpiralsItem> = (0..self.n_arms)
+            .flat_map(|arm| (0..self.n_samples_per_arm).map(move |i| (arm, i as f64 * step)))
+            .map(|(arm, radius)| {
+                let angle = 2.0 * PI * (self.turns * radius + arm as f64 / self.n_arms as f64);
+                let mut x = [radius * angle.cos(), radius * angle.sin()];
+                if let Some(normal) = normal {
+                    x.iter_mut()
+                        .for_each(|value| *value += normal.sample(&mut rng));
+                }
+
+                SpiralsItem {
+                    x: [x[0] as f32, x[1] as f32],
+                    y: arm as i8,
+                }
+            })
+            .collect();
+
+        if self.shuffle {
+            items.shuffle(&mut rng);
+        }
+
+        items
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SpiralsItem {
+    pub x: [f32; 2],
+    pub y: i8,
+}
+
+pub struct SpiralsDataset {
+    dataset: Vec<SpiralsItem>,
+}
+
+impl Dataset<SpiralsItem> for SpiralsDataset {
+    fn get(&self, index: usize) -> Option<SpiralsItem> {
+        self.dataset.get(index).cloned()
+    }
+
+    fn len(&self) -> usize {
+        self.dataset.len()
+    }
+}
+
+pub struct SpiralsDatasetConfig {
+    pub spirals: SpiralsConfig,
+    pub split: SplitConfig,
+}
+
+impl SpiralsDatasetConfig {
+    pub fn train(&self) -> SpiralsDataset {
+        self.dataset(Split::Train)
+    }
+
+    pub fn valid(&self) -> SpiralsDataset {
+        self.dataset(Split::Valid)
+    }
+
+    pub fn test(&self) -> SpiralsDataset {
+        self.dataset(Split::Test)
+    }
+
+    fn dataset(&self, split: Split) -> SpiralsDataset {
+        let items = self.spirals.generate();
+        let splits = self.split.split_by(items, |item| item.y);
+
+        SpiralsDataset {
+            dataset: splits.take(split),
+        }
+    }
+}
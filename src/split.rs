@@ -0,0 +1,118 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+use burn::config::Config;
+use ndarray_rand::rand::seq::SliceRandom;
+use ndarray_rand::rand::{rngs, SeedableRng};
+
+/// Fractions and seed used to partition a list of items into disjoint
+/// train/validation/test splits. Whatever is not assigned to train or
+/// validation ends up in the test split.
+#[derive(Config, Debug)]
+pub struct SplitConfig {
+    pub train: f32,
+    pub valid: f32,
+    pub seed: u64,
+    pub stratify: bool,
+}
+
+/// One of the disjoint partitions of the items.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Split {
+    Train,
+    Valid,
+    Test,
+}
+
+impl Split {
+    /// Lowercase name, e.g. of the directory or the files of the split.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Split::Train => "train",
+            Split::Valid => "valid",
+            Split::Test => "test",
+        }
+    }
+}
+
+impl Display for Split {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Splits<I> {
+    pub train: Vec<I>,
+    pub valid: Vec<I>,
+    pub test: Vec<I>,
+}
+
+impl<I> Splits<I> {
+    pub fn take(self, split: Split) -> Vec<I> {
+        match split {
+            Split::Train => self.train,
+            Split::Valid => self.valid,
+            Split::Test => self.test,
+        }
+    }
+}
+
+impl SplitConfig {
+    /// Shuffles the items and partitions them according to the configured fractions.
+    pub fn split<I>(&self, items: Vec<I>) -> Splits<I> {
+        self.split_by(items, |_| ())
+    }
+
+    /// Same as [`SplitConfig::split`], but when `stratify` is set every split keeps
+    /// the proportion of each `key` found in the original items.
+    pub fn split_by<I, K: Ord>(&self, items: Vec<I>, key: impl Fn(&I) -> K) -> Splits<I> {
+        assert!(
+            self.train >= 0.0 && self.valid >= 0.0 && self.train + self.valid <= 1.0,
+            "Split fractions should be positive and add up to at most 1, got train={} valid={}",
+            self.train,
+            self.valid
+        );
+
+        let mut rng = rngs::StdRng::seed_from_u64(self.seed);
+
+        let groups = match self.stratify {
+            true => {
+                let mut groups = BTreeMap::<K, Vec<I>>::new();
+                for item in items {
+                    groups.entry(key(&item)).or_default().push(item);
+                }
+                groups.into_values().collect()
+            }
+            false => vec![items],
+        };
+
+        let mut splits = Splits {
+            train: Vec::new(),
+            valid: Vec::new(),
+            test: Vec::new(),
+        };
+
+        for mut group in groups {
+            group.shuffle(&mut rng);
+
+            let n = group.len();
+            let n_train = ((self.train * n as f32).round() as usize).min(n);
+            let n_valid = ((self.valid * n as f32).round() as usize).min(n - n_train);
+
+            let mut rest = group.split_off(n_train);
+            let test = rest.split_off(n_valid);
+
+            splits.train.extend(group);
+            splits.valid.extend(rest);
+            splits.test.extend(test);
+        }
+
+        // Stratified groups are appended one after the other
+        splits.train.shuffle(&mut rng);
+        splits.valid.shuffle(&mut rng);
+        splits.test.shuffle(&mut rng);
+
+        splits
+    }
+}
@@ -0,0 +1,533 @@
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+
+use burn::config::{Config, ConfigError};
+use ndarray_rand::rand::rngs::StdRng;
+use ndarray_rand::rand::seq::SliceRandom;
+use ndarray_rand::rand::{Rng, SeedableRng};
+use plotly::{Plot, Trace};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::early_stopping::Mode;
+use crate::overrides::{self, OverrideError};
+use crate::report;
+use crate::runs::{self, RunError, RunManifest};
+use crate::train::ArtifactConfig;
+
+const SWEEP_FILE: &str = "sweep.json";
+
+#[derive(Debug)]
+pub enum SweepError {
+    InvalidFile {
+        path: PathBuf,
+        message: String,
+    },
+    /// Grid searches can only go through lists of values.
+    NotAList {
+        key: String,
+    },
+    EmptyValues {
+        key: String,
+    },
+    /// A random search without any run.
+    NoRuns,
+    /// Bounds that no value can be drawn between.
+    InvalidRange {
+        key: String,
+        message: String,
+    },
+    Override(OverrideError),
+    Run(RunError),
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+}
+
+impl Display for SweepError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SweepError::InvalidFile { path, message } => {
+                write!(f, "Sweep {} is invalid: {message}", path.display())
+            }
+            SweepError::NotAList { key } => {
+                write!(f, "A grid search needs a list of values for {key}")
+            }
+            SweepError::EmptyValues { key } => write!(f, "No values to sweep for {key}"),
+            SweepError::NoRuns => write!(f, "A random search needs at least one run"),
+            SweepError::InvalidRange { key, message } => {
+                write!(f, "Invalid range for {key}: {message}")
+            }
+            SweepError::Override(err) => err.fmt(f),
+            SweepError::Run(err) => err.fmt(f),
+            SweepError::Io { path, source } => {
+                write!(f, "Could not write {}: {source}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for SweepError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SweepError::Override(err) => Some(err),
+            SweepError::Run(err) => Some(err),
+            SweepError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<OverrideError> for SweepError {
+    fn from(err: OverrideError) -> Self {
+        SweepError::Override(err)
+    }
+}
+
+impl From<RunError> for SweepError {
+    fn from(err: RunError) -> Self {
+        SweepError::Run(err)
+    }
+}
+
+/// Values taken by a config field during a sweep.
+#[derive(Config, Debug)]
+pub enum SweepValues {
+    /// Any JSON values, e.g. `[[64], [64, 64]]` for the hidden layers of an MLP.
+    List(Vec<Value>),
+    Uniform {
+        min: f64,
+        max: f64,
+    },
+    /// Uniform over the logarithm of the values, e.g. for learning rates.
+    LogUniform {
+        min: f64,
+        max: f64,
+    },
+    /// Integers between `min` and `max` included.
+    Integer {
+        min: i64,
+        max: i64,
+    },
+}
+
+/// Config field at the dotted path `key`, e.g. `training.learning_rate`, and its values.
+#[derive(Config, Debug)]
+pub struct SweepParameter {
+    pub key: String,
+    pub values: SweepValues,
+}
+
+#[derive(Config, Debug, Copy, PartialEq, Eq)]
+pub enum Search {
+    /// Every combination of the values of the parameters.
+    Grid,
+    /// Runs with values drawn independently for each parameter, without drawing the
+    /// same values twice. Fewer runs are made when the parameters do not have
+    /// `num_runs` different combinations.
+    Random { num_runs: usize },
+}
+
+/// Hyperparameter search run one training at a time, read from a JSON file.
+#[derive(Config, Debug)]
+pub struct SweepConfig {
+    pub parameters: Vec<SweepParameter>,
+    #[config(default = "Search::Grid")]
+    pub search: Search,
+    /// Metric of the run manifests used to rank the runs, e.g. `valid/Accuracy`.
+    #[config(default = "String::from(\"valid/Loss\")")]
+    pub metric: String,
+    #[config(default = "Mode::Min")]
+    pub mode: Mode,
+    /// Seed of the random search.
+    #[config(default = 42)]
+    pub seed: u64,
+}
+
+/// Values of the parameters of one run, in the order of the sweep parameters.
+pub type Trial = Vec<(String, Value)>;
+
+impl SweepConfig {
+    /// Reads the sweep file and checks the values of its parameters.
+    pub fn load_file(path: impl AsRef<Path>) -> Result<Self, SweepError> {
+        let path = path.as_ref();
+        let sweep = Self::load(path).map_err(|err| SweepError::InvalidFile {
+            path: path.to_path_buf(),
+            message: match err {
+                ConfigError::FileNotFound(_) => "file not found".to_string(),
+                ConfigError::InvalidFormat(message) => message,
+            },
+        })?;
+
+        sweep.validate()?;
+        Ok(sweep)
+    }
+
+    /// Checks that every parameter has values to draw from, and that a random search
+    /// has runs.
+    pub fn validate(&self) -> Result<(), SweepError> {
+        if self.search == (Search::Random { num_runs: 0 }) {
+            return Err(SweepError::NoRuns);
+        }
+
+        for parameter in &self.parameters {
+            let key = || parameter.key.clone();
+            let invalid = |message: String| SweepError::InvalidRange {
+                key: key(),
+                message,
+            };
+
+            match &parameter.values {
+                SweepValues::List(values) if values.is_empty() => {
+                    return Err(SweepError::EmptyValues { key: key() })
+                }
+                SweepValues::List(_) => {}
+                SweepValues::Uniform { min, max } | SweepValues::LogUniform { min, max }
+                    if !(min.is_finite() && max.is_finite() && min <= max) =>
+                {
+                    return Err(invalid(format!(
+                        "expected finite bounds with min <= max, got min={min} max={max}"
+                    )))
+                }
+                SweepValues::LogUniform { min, .. } if *min <= 0.0 => {
+                    return Err(invalid(format!(
+                        "a log-uniform range needs a positive min, got {min}"
+                    )))
+                }
+                SweepValues::Integer { min, max } if min > max => {
+                    return Err(invalid(format!(
+                        "expected min <= max, got min={min} max={max}"
+                    )))
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Parameter values of every run of the sweep.
+    pub fn trials(&self) -> Result<Vec<Trial>, SweepError> {
+        self.validate()?;
+
+        match self.search {
+            Search::Grid => {
+                self.parameters
+                    .iter()
+                    .try_fold(vec![Vec::new()], |trials, parameter| {
+                        let SweepValues::List(values) = &parameter.values else {
+                            return Err(SweepError::NotAList {
+                                key: parameter.key.clone(),
+                            });
+                        };
+
+                        Ok(trials
+                            .into_iter()
+                            .flat_map(|trial: Trial| {
+                                values.iter().map(move |value| {
+                                    let mut trial = trial.clone();
+                                    trial.push((parameter.key.clone(), value.clone()));
+                                    trial
+                                })
+                            })
+                            .collect())
+                    })
+            }
+            Search::Random { num_runs } => {
+                // Lists and integers only have so many combinations to draw
+                let num_runs = self
+                    .parameters
+                    .iter()
+                    .try_fold(1usize, |product, parameter| {
+                        Some(product.saturating_mul(parameter.values.num_distinct()?))
+                    })
+                    .map_or(num_runs, |combinations| combinations.min(num_runs));
+
+                let mut rng = StdRng::seed_from_u64(self.seed);
+                let mut trials: Vec<Trial> = Vec::new();
+                while trials.len() < num_runs {
+                    let trial = self
+                        .parameters
+                        .iter()
+                        .map(|parameter| (parameter.key.clone(), parameter.values.sample(&mut rng)))
+                        .collect();
+                    // The same values would train the same run again
+                    if !trials.contains(&trial) {
+                        trials.push(trial);
+                    }
+                }
+                Ok(trials)
+            }
+        }
+    }
+}
+
+impl SweepValues {
+    // Number of different values, `None` when a range has too many to count
+    fn num_distinct(&self) -> Option<usize> {
+        match self {
+            SweepValues::List(values) => Some(
+                values
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, value)| !values[..*i].contains(value))
+                    .count(),
+            ),
+            SweepValues::Uniform { min, max } | SweepValues::LogUniform { min, max } => {
+                (min == max).then_some(1)
+            }
+            SweepValues::Integer { min, max } => {
+                usize::try_from(max.abs_diff(*min)).ok()?.checked_add(1)
+            }
+        }
+    }
+
+    fn sample(&self, rng: &mut StdRng) -> Value {
+        match self {
+            SweepValues::List(values) => values.choose(rng).cloned().unwrap_or(Value::Null),
+            SweepValues::Uniform { min, max } => Value::from(rng.gen_range(*min..=*max)),
+            SweepValues::LogUniform { min, max } => {
+                Value::from(rng.gen_range(min.ln()..=max.ln()).exp())
+            }
+            SweepValues::Integer { min, max } => Value::from(rng.gen_range(*min..=*max)),
+        }
+    }
+}
+
+/// Run of a sweep with its parameter values and the value of the ranking metric.
+#[derive(Clone, Debug)]
+pub struct SweepRun {
+    pub id: String,
+    pub trial: Trial,
+    pub metric: Option<f64>,
+}
+
+/// Runs of a sweep, best first.
+pub struct SweepReport {
+    pub keys: Vec<String>,
+    pub metric: String,
+    pub runs: Vec<SweepRun>,
+}
+
+/// Trains one run per trial in a new `sweep-<time>` directory of `runs_dir`, then
+/// ranks the runs and writes the report in that directory. Every config is checked
+/// before the first training.
+pub fn run_sweep<C: ArtifactConfig>(
+    sweep: &SweepConfig,
+    config: &C,
+    runs_dir: &str,
+    mut train: impl FnMut(&str, C),
+) -> Result<SweepReport, SweepError> {
+    let trials = sweep.trials()?;
+    let configs = trials
+        .iter()
+        .map(|trial| {
+            let overrides: Vec<(String, String)> = trial
+                .iter()
+                .map(|(key, value)| (key.clone(), value.to_string()))
+                .collect();
+            overrides::apply(config, &overrides)
+        })
+        .collect::<Result<Vec<C>, _>>()?;
+
+    let sweep_dir = format!(
+        "{}/sweep-{}",
+        runs_dir.trim_end_matches('/'),
+        runs::timestamp()
+    );
+    let io_error = |path: &str| {
+        let path = PathBuf::from(path);
+        move |source| SweepError::Io { path, source }
+    };
+    std::fs::create_dir_all(&sweep_dir).map_err(io_error(&sweep_dir))?;
+    sweep
+        .save(format!("{sweep_dir}/{SWEEP_FILE}"))
+        .map_err(io_error(&sweep_dir))?;
+
+    let mut runs = Vec::new();
+    for (trial, config) in trials.into_iter().zip(configs) {
+        let run_dir = runs::new_run_dir(&sweep_dir, &config)?;
+        train(&run_dir, config);
+
+        let manifest = RunManifest::load_from(&run_dir).ok();
+        runs.push(SweepRun {
+            id: manifest.as_ref().map_or_else(String::new, |m| m.id.clone()),
+            metric: manifest.and_then(|m| m.metrics.get(&sweep.metric).copied()),
+            trial,
+        });
+    }
+
+    let report = SweepReport::new(sweep, runs);
+    report.write(&sweep_dir)?;
+
+    Ok(report)
+}
+
+impl SweepReport {
+    /// Sorts the runs from the best value of the metric to the worst, runs without
+    /// the metric last.
+    pub fn new(sweep: &SweepConfig, mut runs: Vec<SweepRun>) -> Self {
+        runs.sort_by(|a, b| match (a.metric, b.metric) {
+            (Some(a), Some(b)) => match sweep.mode {
+                Mode::Min => a.total_cmp(&b),
+                Mode::Max => b.total_cmp(&a),
+            },
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        });
+
+        Self {
+            keys: sweep.parameters.iter().map(|p| p.key.clone()).collect(),
+            metric: sweep.metric.clone(),
+            runs,
+        }
+    }
+
+    /// One row per run with its rank, parameter values and metric, with a header.
+    pub fn to_csv(&self) -> String {
+        let mut rows = vec![["rank", "run"]
+            .into_iter()
+            .map(String::from)
+            .chain(self.keys.iter().cloned())
+            .chain([self.metric.clone()])
+            .collect::<Vec<_>>()];
+
+        for (rank, run) in self.runs.iter().enumerate() {
+            rows.push(
+                [(rank + 1).to_string(), run.id.clone()]
+                    .into_iter()
+                    .chain(run.trial.iter().map(|(_, value)| csv_cell(value)))
+                    .chain([run.metric.map(|m| m.to_string()).unwrap_or_default()])
+                    .collect(),
+            );
+        }
+
+        rows.iter().map(|row| row.join(",") + "\n").collect()
+    }
+
+    /// Parallel coordinates with one axis per parameter and one for the metric, each
+    /// line being a run colored by its metric.
+    pub fn plot(&self) -> Plot {
+        let mut dimensions: Vec<Dimension> = self
+            .keys
+            .iter()
+            .enumerate()
+            .map(|(i, key)| {
+                let values: Vec<&Value> = self.runs.iter().map(|run| &run.trial[i].1).collect();
+                Dimension::new(key, &values)
+            })
+            .collect();
+        let metric: Vec<f64> = self
+            .runs
+            .iter()
+            .map(|run| run.metric.unwrap_or(f64::NAN))
+            .collect();
+        dimensions.push(Dimension {
+            label: self.metric.clone(),
+            values: metric.clone(),
+            tickvals: None,
+            ticktext: None,
+        });
+
+        let mut plot = Plot::new();
+        plot.add_trace(Box::new(ParallelCoordinates {
+            r#type: "parcoords",
+            line: Line {
+                color: metric,
+                colorscale: "Viridis",
+                showscale: true,
+            },
+            dimensions,
+        }));
+        plot
+    }
+
+    /// Writes `sweep.csv` and `sweep.html` in `dir`.
+    pub fn write(&self, dir: impl AsRef<Path>) -> Result<(), SweepError> {
+        let dir = dir.as_ref();
+
+        let mut plot = self.plot();
+        plot.use_local_plotly();
+        plot.write_html(dir.join("sweep.html"));
+
+        let path = dir.join("sweep.csv");
+        std::fs::write(&path, self.to_csv()).map_err(|source| SweepError::Io { path, source })
+    }
+}
+
+// Same rows as the CSV, with aligned columns
+impl Display for SweepReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        report::write_table(f, &self.to_csv())
+    }
+}
+
+// Strings without their quotes, other values in JSON without commas
+fn csv_cell(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        value => value.to_string().replace(',', " "),
+    }
+}
+
+// Plotly's parcoords trace, which the plotly crate does not provide
+#[derive(Clone, Serialize)]
+struct ParallelCoordinates {
+    r#type: &'static str,
+    line: Line,
+    dimensions: Vec<Dimension>,
+}
+
+#[derive(Clone, Serialize)]
+struct Line {
+    color: Vec<f64>,
+    colorscale: &'static str,
+    showscale: bool,
+}
+
+#[derive(Clone, Serialize)]
+struct Dimension {
+    label: String,
+    values: Vec<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tickvals: Option<Vec<f64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ticktext: Option<Vec<String>>,
+}
+
+impl Dimension {
+    // Numbers are plotted as they are, other values as categories
+    fn new(label: &str, values: &[&Value]) -> Self {
+        if let Some(numbers) = values.iter().map(|value| value.as_f64()).collect() {
+            return Self {
+                label: label.to_string(),
+                values: numbers,
+                tickvals: None,
+                ticktext: None,
+            };
+        }
+
+        let mut categories: Vec<String> = values.iter().map(|value| csv_cell(value)).collect();
+        categories.sort();
+        categories.dedup();
+        let index = |value: &Value| {
+            let cell = csv_cell(value);
+            categories.iter().position(|c| *c == cell).unwrap() as f64
+        };
+
+        Self {
+            label: label.to_string(),
+            values: values.iter().map(|value| index(value)).collect(),
+            tickvals: Some((0..categories.len()).map(|i| i as f64).collect()),
+            ticktext: Some(categories),
+        }
+    }
+}
+
+impl Trace for ParallelCoordinates {
+    fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Trace should be serialized successfully")
+    }
+}
@@ -1,16 +1,18 @@
+use burn::config::Config;
 use burn::data::dataloader::batcher::Batcher;
 use burn::data::dataset::Dataset;
 use burn::prelude::{Float, Tensor};
 use burn::tensor::backend::Backend;
 use burn::tensor::Data;
 use ndarray::{Array, Ix1};
-use ndarray_rand::rand::seq::IteratorRandom;
 use ndarray_rand::rand::SeedableRng;
 use ndarray_rand::{
     rand,
     rand_distr::{Distribution, Normal},
 };
 
+use crate::split::{Split, SplitConfig};
+
 pub fn make_toydata(start: f32, end: f32, n: usize) -> Vec<ToyItem> {
     let x = Array::<f32, Ix1>::linspace(start, end, n);
 
@@ -18,13 +20,10 @@ pub fn make_toydata(start: f32, end: f32, n: usize) -> Vec<ToyItem> {
     let mut rng = rand::rngs::StdRng::seed_from_u64(21);
     let y = x.mapv(|x| x + x.sin() + normal.sample(&mut rng));
 
-    let toy_items = x
-        .into_iter()
-        .zip(y.into_iter())
+    x.into_iter()
+        .zip(y)
         .map(|(x, y)| ToyItem { x, y })
-        .collect();
-
-    toy_items
+        .collect()
 }
 
 #[derive(Clone, Debug)]
@@ -47,37 +46,33 @@ impl Dataset<ToyItem> for ToyDataset {
     }
 }
 
+/// Samples of `x + sin(x)` with gaussian noise, see [`make_toydata`].
+#[derive(Config, Debug)]
 pub struct ToyDatasetConfig {
     pub start: f32,
     pub end: f32,
     pub n: usize,
-    pub split: f32,
+    pub split: SplitConfig,
 }
 
 impl ToyDatasetConfig {
     pub fn train(&self) -> ToyDataset {
-        self.new("train")
+        self.dataset(Split::Train)
+    }
+    pub fn valid(&self) -> ToyDataset {
+        self.dataset(Split::Valid)
     }
     pub fn test(&self) -> ToyDataset {
-        self.new("test")
+        self.dataset(Split::Test)
     }
 
-    fn new(&self, split: &str) -> ToyDataset {
+    fn dataset(&self, split: Split) -> ToyDataset {
         let toy_items = make_toydata(self.start, self.end, self.n);
+        let splits = self.split.split(toy_items);
 
-        let amount = (self.split * self.n as f32).ceil() as usize;
-        let mut rng = rand::rngs::StdRng::seed_from_u64(12);
-
-        let items = match split {
-            "train" => toy_items.into_iter().choose_multiple(&mut rng, amount),
-            _ => toy_items
-                .into_iter()
-                .choose_multiple(&mut rng, self.n)
-                .drain(0..amount)
-                .collect(),
-        };
-
-        ToyDataset { dataset: items }
+        ToyDataset {
+            dataset: splits.take(split),
+        }
     }
 }
 
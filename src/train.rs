@@ -0,0 +1,401 @@
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use burn::{
+    config::{Config, ConfigError},
+    data::{
+        dataloader::{batcher::Batcher, DataLoader, DataLoaderBuilder},
+        dataset::Dataset,
+    },
+    lr_scheduler::LrScheduler,
+    module::AutodiffModule,
+    optim::Optimizer,
+    record::CompactRecorder,
+    tensor::backend::AutodiffBackend,
+    train::{
+        logger::{FileMetricLogger, MetricLogger},
+        metric::{AccuracyMetric, LearningRateMetric, LossMetric},
+        ClassificationOutput, LearnerBuilder, RegressionOutput, TrainStep, ValidStep,
+    },
+};
+
+use crate::early_stopping::EarlyStoppingConfig;
+use crate::history::{EpochValue, TrainingHistory};
+use crate::optimizer::OptimizerConfig;
+use crate::runs::Run;
+use crate::scheduler::{Scheduler, SchedulerConfig};
+
+const METADATA_FILE: &str = "metadata.json";
+
+/// Hyperparameters shared by the learners of every chapter.
+#[derive(Config)]
+pub struct TrainingConfig {
+    pub optimizer: OptimizerConfig,
+    #[config(default = 10)]
+    pub num_epochs: usize,
+    #[config(default = 64)]
+    pub batch_size: usize,
+    #[config(default = 4)]
+    pub num_workers: usize,
+    #[config(default = 42)]
+    pub seed: u64,
+    /// Initial or peak learning rate, depending on the scheduler.
+    #[config(default = 1.0e-4)]
+    pub learning_rate: f64,
+    #[config(default = "SchedulerConfig::Constant")]
+    pub scheduler: SchedulerConfig,
+    #[config(default = "EarlyStoppingConfig::new()")]
+    pub early_stopping: EarlyStoppingConfig,
+}
+
+/// Outcome of a training saved as `metadata.json` in the artifact directory.
+#[derive(Config, Debug)]
+pub struct ArtifactMetadata {
+    /// Validation metric used to pick the best epoch.
+    pub metric: String,
+    /// Epoch of the saved model, the last one when no epoch has the metric.
+    pub epoch: usize,
+    /// Value of the metric at that epoch.
+    pub value: Option<f64>,
+    /// Last trained epoch, before `num_epochs` when the training stopped early.
+    pub last_epoch: usize,
+}
+
+/// Configuration saved as `config.json` in the artifact directory, usually the
+/// model configuration of a chapter together with its [`TrainingConfig`].
+pub trait ArtifactConfig: Config {
+    fn training(&self) -> &TrainingConfig;
+
+    /// Description of the data the model is trained on, for the dataset fingerprint
+    /// of the run manifest. Empty when the data only depends on the example's code.
+    fn dataset(&self) -> String {
+        String::new()
+    }
+
+    /// Files the data is read from, see [`dataset_fingerprint`](crate::runs::dataset_fingerprint).
+    fn data_files(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
+}
+
+impl TrainingConfig {
+    /// Builds a shuffled data loader using the configured batch size, workers and seed.
+    pub fn dataloader<I, O, B, D>(&self, batcher: B, dataset: D) -> Arc<dyn DataLoader<O>>
+    where
+        I: Send + Sync + Clone + std::fmt::Debug + 'static,
+        O: Send + Clone + std::fmt::Debug + 'static,
+        B: Batcher<I, O> + Clone + 'static,
+        D: Dataset<I> + 'static,
+    {
+        DataLoaderBuilder::new(batcher)
+            .batch_size(self.batch_size)
+            .shuffle(self.seed)
+            .num_workers(self.num_workers)
+            .build(dataset)
+    }
+
+    /// Returns the learning rate scheduler for a training data loader of
+    /// `num_items` items.
+    pub fn scheduler(&self, num_items: usize) -> Scheduler {
+        self.scheduler.init(
+            self.learning_rate,
+            self.num_epochs,
+            num_items.div_ceil(self.batch_size),
+        )
+    }
+}
+
+impl ArtifactMetadata {
+    pub fn save_to(&self, artifact_dir: &str) -> std::io::Result<()> {
+        self.save(format!("{artifact_dir}/{METADATA_FILE}"))
+    }
+
+    pub fn load_from(artifact_dir: &str) -> Result<Self, ConfigError> {
+        Self::load(format!("{artifact_dir}/{METADATA_FILE}"))
+    }
+}
+
+pub fn create_artifact_dir(artifact_dir: &str) {
+    // Remove existing artifacts before to get an accurate learner summary
+    std::fs::remove_dir_all(artifact_dir).ok();
+    std::fs::create_dir_all(artifact_dir).ok();
+}
+
+/// Trains a classifier tracking accuracy and loss, then saves the model of the best
+/// epoch next to the config and checkpoints in `artifact_dir`, see
+/// [`EarlyStoppingConfig`]. Training starts over in a clean directory unless it
+/// resumes from the given `checkpoint` epoch.
+pub fn train_classifier<B, C, M, TI, VI>(
+    artifact_dir: &str,
+    config: &C,
+    model: impl FnOnce(&B::Device) -> M,
+    dataloader_train: Arc<dyn DataLoader<TI>>,
+    dataloader_valid: Arc<dyn DataLoader<VI>>,
+    device: B::Device,
+    checkpoint: Option<usize>,
+) -> M
+where
+    B: AutodiffBackend,
+    C: ArtifactConfig,
+    M: AutodiffModule<B> + TrainStep<TI, ClassificationOutput<B>> + Display + 'static,
+    M::InnerModule: ValidStep<VI, ClassificationOutput<B::InnerBackend>>,
+    TI: Send + 'static,
+    VI: Send + 'static,
+{
+    let training = config.training();
+    let run = start::<B, C>(
+        artifact_dir,
+        config,
+        &["Accuracy", "Loss"],
+        [dataloader_train.num_items(), dataloader_valid.num_items()],
+        checkpoint,
+    );
+
+    let learner = learner_builder(artifact_dir, training, &device, checkpoint)
+        .metric_train_numeric(AccuracyMetric::new())
+        .metric_valid_numeric(AccuracyMetric::new())
+        .metric_train_numeric(LossMetric::new())
+        .metric_valid_numeric(LossMetric::new())
+        .metric_train_numeric(LearningRateMetric::new())
+        .build(
+            model(&device),
+            training.optimizer.init(),
+            training.scheduler(dataloader_train.num_items()),
+        );
+
+    let trained_model = learner.fit(dataloader_train, dataloader_valid);
+
+    finish(artifact_dir, training, run, trained_model, &device)
+}
+
+/// Trains a regression model tracking the loss, then saves the model of the best
+/// epoch next to the config and checkpoints in `artifact_dir`, see
+/// [`train_classifier`].
+pub fn train_regressor<B, C, M, TI, VI>(
+    artifact_dir: &str,
+    config: &C,
+    model: impl FnOnce(&B::Device) -> M,
+    dataloader_train: Arc<dyn DataLoader<TI>>,
+    dataloader_valid: Arc<dyn DataLoader<VI>>,
+    device: B::Device,
+    checkpoint: Option<usize>,
+) -> M
+where
+    B: AutodiffBackend,
+    C: ArtifactConfig,
+    M: AutodiffModule<B> + TrainStep<TI, RegressionOutput<B>> + Display + 'static,
+    M::InnerModule: ValidStep<VI, RegressionOutput<B::InnerBackend>>,
+    TI: Send + 'static,
+    VI: Send + 'static,
+{
+    let training = config.training();
+    let run = start::<B, C>(
+        artifact_dir,
+        config,
+        &["Loss"],
+        [dataloader_train.num_items(), dataloader_valid.num_items()],
+        checkpoint,
+    );
+
+    let learner = learner_builder(artifact_dir, training, &device, checkpoint)
+        .metric_train_numeric(LossMetric::new())
+        .metric_valid_numeric(LossMetric::new())
+        .metric_train_numeric(LearningRateMetric::new())
+        .build(
+            model(&device),
+            training.optimizer.init(),
+            training.scheduler(dataloader_train.num_items()),
+        );
+
+    let trained_model = learner.fit(dataloader_train, dataloader_valid);
+
+    finish(artifact_dir, training, run, trained_model, &device)
+}
+
+/// Returns the last epoch with a saved checkpoint in the artifact directory.
+pub fn latest_checkpoint(artifact_dir: &str) -> Option<usize> {
+    std::fs::read_dir(format!("{artifact_dir}/checkpoint"))
+        .ok()?
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name();
+            let epoch = name
+                .to_str()?
+                .strip_prefix("model-")?
+                .strip_suffix(".mpk")?;
+            epoch.parse::<usize>().ok()
+        })
+        .max()
+}
+
+// Checks the early stopping metric against those the learner tracks, then saves the
+// config in a clean artifact directory, unless resuming, and starts the run manifest
+fn start<B: AutodiffBackend, C: ArtifactConfig>(
+    artifact_dir: &str,
+    config: &C,
+    metrics: &[&str],
+    num_items: [usize; 2],
+    checkpoint: Option<usize>,
+) -> Run {
+    config.training().early_stopping.check_metric(metrics);
+
+    if checkpoint.is_none() {
+        create_artifact_dir(artifact_dir);
+    }
+
+    config
+        .save(format!("{artifact_dir}/config.json"))
+        .expect("Config should be saved successfully");
+
+    B::seed(config.training().seed);
+
+    Run::start(artifact_dir, config, B::name(), num_items)
+}
+
+// Writes the history and the best model of a finished training, then completes the
+// run manifest with the metrics of the saved epoch
+fn finish<B: AutodiffBackend, M: AutodiffModule<B>>(
+    artifact_dir: &str,
+    training: &TrainingConfig,
+    run: Run,
+    trained_model: M,
+    device: &B::Device,
+) -> M {
+    let history = write_history(artifact_dir);
+    let (model, metadata) =
+        save_best_model(artifact_dir, training, &history, trained_model, device);
+    run.finish(artifact_dir, &history, &metadata);
+
+    model
+}
+
+fn learner_builder<B, T, V, M, O, S>(
+    artifact_dir: &str,
+    training: &TrainingConfig,
+    device: &B::Device,
+    checkpoint: Option<usize>,
+) -> LearnerBuilder<B, T, V, M, O, S>
+where
+    B: AutodiffBackend,
+    T: Send + 'static,
+    V: Send + 'static,
+    M: AutodiffModule<B> + Display + 'static,
+    O: Optimizer<M, B>,
+    S: LrScheduler<B>,
+    M::Record: 'static,
+    O::Record: 'static,
+    S::Record: 'static,
+{
+    let mut builder = LearnerBuilder::new(artifact_dir)
+        .with_file_checkpointer(CompactRecorder::new())
+        .devices(vec![device.clone()])
+        .num_epochs(training.num_epochs)
+        .summary();
+    let early_stopping = &training.early_stopping;
+    let best = checkpoint.and_then(|epoch| resumed_best(artifact_dir, early_stopping, epoch));
+    builder.with_checkpointing_strategy(match checkpoint {
+        Some(epoch) => early_stopping.checkpointing().resumed(best.clone(), epoch),
+        None => early_stopping.checkpointing(),
+    });
+
+    let builder = match early_stopping.early_stopping() {
+        Some(strategy) => builder.early_stopping(strategy.with_best(best)),
+        None => builder,
+    };
+
+    match checkpoint {
+        Some(epoch) => builder.checkpoint(epoch).metric_loggers(
+            resumed_logger(&format!("{artifact_dir}/train"), epoch),
+            resumed_logger(&format!("{artifact_dir}/valid"), epoch),
+        ),
+        None => builder,
+    }
+}
+
+// Best value of the metric up to the checkpoint, which the strategies only keep in memory
+fn resumed_best(
+    artifact_dir: &str,
+    early_stopping: &EarlyStoppingConfig,
+    checkpoint: usize,
+) -> Option<EpochValue> {
+    let history = TrainingHistory::load(artifact_dir).ok()?;
+    let values: Vec<EpochValue> = history
+        .get("valid", &early_stopping.metric)?
+        .epochs
+        .iter()
+        .filter(|value| value.epoch <= checkpoint)
+        .cloned()
+        .collect();
+
+    early_stopping.best(&values)
+}
+
+// The default file loggers count the epochs from 1, so a resumed training would
+// overwrite the logs of the first epochs instead of adding those after the checkpoint
+fn resumed_logger(dir: &str, checkpoint: usize) -> FileMetricLogger {
+    let mut logger = FileMetricLogger::new(dir);
+    logger.end_epoch(checkpoint);
+    logger
+}
+
+// The learner has joined its metric loggers once `fit` returns, so the logs are complete
+fn write_history(artifact_dir: &str) -> TrainingHistory {
+    TrainingHistory::load(artifact_dir)
+        .and_then(|history| history.write(artifact_dir).map(|_| history))
+        .expect("Training history should be written successfully")
+}
+
+// Saves the checkpoint of the best epoch as the model, or the trained model when that
+// checkpoint is missing, and records the epoch in the metadata
+fn save_best_model<B: AutodiffBackend, M: AutodiffModule<B>>(
+    artifact_dir: &str,
+    training: &TrainingConfig,
+    history: &TrainingHistory,
+    trained_model: M,
+    device: &B::Device,
+) -> (M, ArtifactMetadata) {
+    let early_stopping = &training.early_stopping;
+    let last_epoch = history
+        .metrics
+        .iter()
+        .filter_map(|metric| metric.epochs.last())
+        .map(|value| value.epoch)
+        .max()
+        .unwrap_or(training.num_epochs);
+
+    let best = history
+        .get("valid", &early_stopping.metric)
+        .and_then(|metric| early_stopping.best(&metric.epochs))
+        .and_then(|best| {
+            let model = trained_model
+                .clone()
+                .load_file(
+                    format!("{artifact_dir}/checkpoint/model-{}", best.epoch),
+                    &CompactRecorder::new(),
+                    device,
+                )
+                .ok()?;
+            Some((model, best))
+        });
+    let (model, best) = match best {
+        Some((model, best)) => (model, Some(best)),
+        None => (trained_model, None),
+    };
+
+    model
+        .clone()
+        .save_file(format!("{artifact_dir}/model"), &CompactRecorder::new())
+        .expect("Trained model should be saved successfully");
+
+    let metadata = ArtifactMetadata {
+        metric: early_stopping.metric.clone(),
+        epoch: best.as_ref().map_or(last_epoch, |best| best.epoch),
+        value: best.map(|EpochValue { value, .. }| value),
+        last_epoch,
+    };
+    metadata
+        .save_to(artifact_dir)
+        .expect("Artifact metadata should be saved successfully");
+
+    (model, metadata)
+}
@@ -0,0 +1,475 @@
+use std::fmt::Display;
+
+use burn::config::Config;
+
+/// Pixels of an image stored channel first, i.e. `data[(c * height + y) * width + x]`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Image {
+    pub channels: usize,
+    pub height: usize,
+    pub width: usize,
+    pub data: Vec<f32>,
+}
+
+impl Image {
+    pub fn new(channels: usize, height: usize, width: usize, data: Vec<f32>) -> Self {
+        assert_eq!(
+            data.len(),
+            channels * height * width,
+            "Image data should have {channels}x{height}x{width} pixels"
+        );
+
+        Self {
+            channels,
+            height,
+            width,
+            data,
+        }
+    }
+
+    /// Image of the given shape where every pixel has the same value.
+    pub fn filled(channels: usize, height: usize, width: usize, value: f32) -> Self {
+        Self::new(
+            channels,
+            height,
+            width,
+            vec![value; channels * height * width],
+        )
+    }
+
+    pub fn shape(&self) -> [usize; 3] {
+        [self.channels, self.height, self.width]
+    }
+
+    pub fn get(&self, c: usize, y: usize, x: usize) -> f32 {
+        self.data[(c * self.height + y) * self.width + x]
+    }
+
+    pub fn set(&mut self, c: usize, y: usize, x: usize, value: f32) {
+        self.data[(c * self.height + y) * self.width + x] = value;
+    }
+
+    /// Pixels of channel `c`.
+    pub fn channel(&self, c: usize) -> &[f32] {
+        let size = self.height * self.width;
+        &self.data[c * size..(c + 1) * size]
+    }
+}
+
+impl<const H: usize, const W: usize> From<&[[f32; W]; H]> for Image {
+    /// Grayscale image with a single channel, e.g. from a `MnistItem`.
+    fn from(image: &[[f32; W]; H]) -> Self {
+        Self::new(1, H, W, image.iter().flatten().copied().collect())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum TransformError {
+    /// The normalization has neither one statistic nor one per channel.
+    Statistics {
+        channels: usize,
+        mean: usize,
+        std: usize,
+    },
+    /// The crop window is not inside the image.
+    Crop {
+        top: usize,
+        left: usize,
+        height: usize,
+        width: usize,
+        image: [usize; 2],
+    },
+    /// Resizing from or to an image without pixels.
+    Resize { from: [usize; 2], to: [usize; 2] },
+    /// The channels cannot be repeated a whole number of times.
+    RepeatChannels { from: usize, to: usize },
+}
+
+impl Display for TransformError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TransformError::Statistics {
+                channels,
+                mean,
+                std,
+            } => write!(
+                f,
+                "Cannot normalize {channels} channels with {mean} means and {std} stds, \
+                 expected 1 or {channels} of each"
+            ),
+            TransformError::Crop {
+                top,
+                left,
+                height,
+                width,
+                image: [image_height, image_width],
+            } => write!(
+                f,
+                "Crop {height}x{width} at ({top}, {left}) is outside of the \
+                 {image_height}x{image_width} image"
+            ),
+            TransformError::Resize {
+                from: [from_height, from_width],
+                to: [to_height, to_width],
+            } => write!(
+                f,
+                "Cannot resize a {from_height}x{from_width} image to {to_height}x{to_width}"
+            ),
+            TransformError::RepeatChannels { from, to } => {
+                write!(f, "Cannot repeat {from} channels into {to}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TransformError {}
+
+/// Preprocessing step applied to each image, see [`TransformPipeline`].
+#[derive(Config, Debug)]
+pub enum Transform {
+    /// Multiplies every pixel, e.g. by `1 / 255` to get values in `[0, 1]`.
+    Scale { factor: f32 },
+    /// Subtracts the mean and divides by the standard deviation of each channel. A
+    /// single value is used for every channel.
+    Normalize { mean: Vec<f32>, std: Vec<f32> },
+    /// Adds borders filled with `value`.
+    Pad {
+        top: usize,
+        bottom: usize,
+        left: usize,
+        right: usize,
+        value: f32,
+    },
+    /// Keeps the `height` x `width` window starting at `(top, left)`.
+    Crop {
+        top: usize,
+        left: usize,
+        height: usize,
+        width: usize,
+    },
+    /// Keeps the `height` x `width` window at the center of the image.
+    CenterCrop { height: usize, width: usize },
+    /// Bilinear resize to `height` x `width`.
+    Resize { height: usize, width: usize },
+    /// Averages the channels into a single one.
+    Grayscale,
+    /// Repeats the channels, e.g. to feed grayscale images to an RGB model.
+    RepeatChannels { channels: usize },
+}
+
+impl Transform {
+    /// Transforms the image, or returns why this transform does not fit its shape.
+    pub fn apply(&self, image: Image) -> Result<Image, TransformError> {
+        self.output_shape(image.shape())?;
+
+        let image = match self {
+            Transform::Scale { factor } => Image {
+                data: image.data.iter().map(|pixel| pixel * factor).collect(),
+                ..image
+            },
+            Transform::Normalize { mean, std } => normalize(image, mean, std),
+            Transform::Pad {
+                top,
+                bottom,
+                left,
+                right,
+                value,
+            } => {
+                let mut padded = Image::filled(
+                    image.channels,
+                    image.height + top + bottom,
+                    image.width + left + right,
+                    *value,
+                );
+                for c in 0..image.channels {
+                    for y in 0..image.height {
+                        for x in 0..image.width {
+                            padded.set(c, y + top, x + left, image.get(c, y, x));
+                        }
+                    }
+                }
+                padded
+            }
+            Transform::Crop {
+                top,
+                left,
+                height,
+                width,
+            } => crop(&image, *top, *left, *height, *width),
+            Transform::CenterCrop { height, width } => {
+                let top = (image.height - height) / 2;
+                let left = (image.width - width) / 2;
+                crop(&image, top, left, *height, *width)
+            }
+            Transform::Resize { height, width } => resize(&image, *height, *width),
+            Transform::Grayscale => {
+                let size = image.height * image.width;
+                let data = (0..size)
+                    .map(|i| {
+                        (0..image.channels)
+                            .map(|c| image.data[c * size + i])
+                            .sum::<f32>()
+                            / image.channels as f32
+                    })
+                    .collect();
+                Image::new(1, image.height, image.width, data)
+            }
+            Transform::RepeatChannels { channels } => {
+                let data = (0..*channels)
+                    .flat_map(|c| image.channel(c % image.channels).to_vec())
+                    .collect();
+                Image::new(*channels, image.height, image.width, data)
+            }
+        };
+
+        Ok(image)
+    }
+
+    /// Shape of the output for an input of shape `[channels, height, width]`, or why
+    /// this transform does not fit it.
+    pub fn output_shape(
+        &self,
+        [channels, height, width]: [usize; 3],
+    ) -> Result<[usize; 3], TransformError> {
+        let crop = |top: usize, left: usize, crop_height: usize, crop_width: usize| {
+            if top + crop_height <= height && left + crop_width <= width {
+                Ok([channels, crop_height, crop_width])
+            } else {
+                Err(TransformError::Crop {
+                    top,
+                    left,
+                    height: crop_height,
+                    width: crop_width,
+                    image: [height, width],
+                })
+            }
+        };
+
+        match self {
+            Transform::Scale { .. } => Ok([channels, height, width]),
+            Transform::Normalize { mean, std } => {
+                let fits = |values: &[f32]| values.len() == 1 || values.len() == channels;
+                if fits(mean) && fits(std) {
+                    Ok([channels, height, width])
+                } else {
+                    Err(TransformError::Statistics {
+                        channels,
+                        mean: mean.len(),
+                        std: std.len(),
+                    })
+                }
+            }
+            Transform::Pad {
+                top,
+                bottom,
+                left,
+                right,
+                ..
+            } => Ok([channels, height + top + bottom, width + left + right]),
+            Transform::Crop {
+                top,
+                left,
+                height,
+                width,
+            } => crop(*top, *left, *height, *width),
+            Transform::CenterCrop {
+                height: crop_height,
+                width: crop_width,
+            } => crop(
+                height.saturating_sub(*crop_height) / 2,
+                width.saturating_sub(*crop_width) / 2,
+                *crop_height,
+                *crop_width,
+            ),
+            Transform::Resize {
+                height: resized_height,
+                width: resized_width,
+            } => {
+                if [height, width, *resized_height, *resized_width].contains(&0) {
+                    Err(TransformError::Resize {
+                        from: [height, width],
+                        to: [*resized_height, *resized_width],
+                    })
+                } else {
+                    Ok([channels, *resized_height, *resized_width])
+                }
+            }
+            Transform::Grayscale => Ok([1, height, width]),
+            Transform::RepeatChannels { channels: repeated } => {
+                if channels > 0 && repeated % channels == 0 {
+                    Ok([*repeated, height, width])
+                } else {
+                    Err(TransformError::RepeatChannels {
+                        from: channels,
+                        to: *repeated,
+                    })
+                }
+            }
+        }
+    }
+}
+
+/// Transforms applied in order by the image batchers.
+#[derive(Config, Debug)]
+pub struct TransformPipeline {
+    pub transforms: Vec<Transform>,
+}
+
+/// Mean and standard deviation of each channel, as used by [`Transform::Normalize`].
+#[derive(Config, Debug)]
+pub struct ImageStatistics {
+    pub mean: Vec<f32>,
+    pub std: Vec<f32>,
+}
+
+impl TransformPipeline {
+    /// Pipeline that keeps the images untouched.
+    pub fn identity() -> Self {
+        Self::new(Vec::new())
+    }
+
+    /// Scales pixels to `[0, 1]` and standardizes them with the statistics of the
+    /// MNIST training set, mean 0.1307 and std 0.3081, as in the PyTorch MNIST example
+    /// https://github.com/pytorch/examples/blob/54f4572509891883a947411fd7239237dd2a39c3/mnist/main.py#L122
+    pub fn mnist() -> Self {
+        Self::new(vec![
+            Transform::Scale {
+                factor: 1.0 / 255.0,
+            },
+            Transform::Normalize {
+                mean: vec![0.1307],
+                std: vec![0.3081],
+            },
+        ])
+    }
+
+    /// Appends a transform at the end of the pipeline.
+    pub fn then(mut self, transform: Transform) -> Self {
+        self.transforms.push(transform);
+        self
+    }
+
+    /// Appends the normalization with the given statistics.
+    pub fn normalize(self, statistics: &ImageStatistics) -> Self {
+        self.then(Transform::Normalize {
+            mean: statistics.mean.clone(),
+            std: statistics.std.clone(),
+        })
+    }
+
+    /// Applies every transform in order, or returns the first that does not fit the
+    /// shape of the image it gets.
+    pub fn apply(&self, image: Image) -> Result<Image, TransformError> {
+        self.transforms
+            .iter()
+            .try_fold(image, |image, transform| transform.apply(image))
+    }
+
+    /// Shape of the images once transformed, which also checks every transform
+    /// against the shape it gets without transforming any image.
+    pub fn output_shape(&self, shape: [usize; 3]) -> Result<[usize; 3], TransformError> {
+        self.transforms
+            .iter()
+            .try_fold(shape, |shape, transform| transform.output_shape(shape))
+    }
+
+    /// Computes the statistics of the images once transformed by this pipeline, e.g.
+    /// `TransformPipeline::new(vec![Transform::Scale { factor: 1.0 / 255.0 }])` gives
+    /// the statistics to normalize a new grayscale dataset with.
+    pub fn statistics(
+        &self,
+        images: impl IntoIterator<Item = Image>,
+    ) -> Result<ImageStatistics, TransformError> {
+        // Welford's algorithm, E[x²] - E[x]² can go negative through rounding
+        let mut count: Vec<f64> = Vec::new();
+        let mut mean: Vec<f64> = Vec::new();
+        let mut m2: Vec<f64> = Vec::new();
+
+        for image in images {
+            let image = self.apply(image)?;
+            if count.len() < image.channels {
+                count.resize(image.channels, 0.0);
+                mean.resize(image.channels, 0.0);
+                m2.resize(image.channels, 0.0);
+            }
+
+            for c in 0..image.channels {
+                for &pixel in image.channel(c) {
+                    let pixel = pixel as f64;
+                    count[c] += 1.0;
+                    let delta = pixel - mean[c];
+                    mean[c] += delta / count[c];
+                    m2[c] += delta * (pixel - mean[c]);
+                }
+            }
+        }
+
+        let std = m2
+            .iter()
+            .zip(&count)
+            .map(|(m2, count)| (m2 / count).sqrt() as f32)
+            .collect();
+
+        Ok(ImageStatistics::new(
+            mean.into_iter().map(|mean| mean as f32).collect(),
+            std,
+        ))
+    }
+}
+
+fn normalize(image: Image, mean: &[f32], std: &[f32]) -> Image {
+    let size = image.height * image.width;
+    let statistic = |values: &[f32], c: usize| match values {
+        [value] => *value,
+        values => values[c],
+    };
+
+    let data = image
+        .data
+        .iter()
+        .enumerate()
+        .map(|(i, pixel)| {
+            let c = i / size;
+            (pixel - statistic(mean, c)) / statistic(std, c)
+        })
+        .collect();
+
+    Image { data, ..image }
+}
+
+fn crop(image: &Image, top: usize, left: usize, height: usize, width: usize) -> Image {
+    let mut cropped = Image::filled(image.channels, height, width, 0.0);
+    for c in 0..image.channels {
+        for y in 0..height {
+            for x in 0..width {
+                cropped.set(c, y, x, image.get(c, y + top, x + left));
+            }
+        }
+    }
+    cropped
+}
+
+// Bilinear interpolation sampling pixel centers, like `align_corners=False` in PyTorch
+fn resize(image: &Image, height: usize, width: usize) -> Image {
+    let scale_y = image.height as f32 / height as f32;
+    let scale_x = image.width as f32 / width as f32;
+    let source = |position: usize, scale: f32, size: usize| {
+        let position = ((position as f32 + 0.5) * scale - 0.5).clamp(0.0, (size - 1) as f32);
+        let low = position.floor() as usize;
+        (low, (low + 1).min(size - 1), position - low as f32)
+    };
+
+    let mut resized = Image::filled(image.channels, height, width, 0.0);
+    for y in 0..height {
+        let (y0, y1, dy) = source(y, scale_y, image.height);
+        for x in 0..width {
+            let (x0, x1, dx) = source(x, scale_x, image.width);
+            for c in 0..image.channels {
+                let top = image.get(c, y0, x0) * (1.0 - dx) + image.get(c, y0, x1) * dx;
+                let bottom = image.get(c, y1, x0) * (1.0 - dx) + image.get(c, y1, x1) * dx;
+                resized.set(c, y, x, top * (1.0 - dy) + bottom * dy);
+            }
+        }
+    }
+    resized
+}
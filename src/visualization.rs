@@ -0,0 +1,176 @@
+use burn::{
+    config::Config,
+    tensor::{activation::softmax, backend::Backend, Data, Shape, Tensor},
+};
+use plotly::{
+    color::NamedColor,
+    common::{ColorScale, ColorScalePalette, Marker, Mode, Visible},
+    Contour, HeatMap, Plot, Scatter,
+};
+
+// Colors of the overlaid points, cycled when there are more classes
+const CLASS_COLORS: [NamedColor; 6] = [
+    NamedColor::Red,
+    NamedColor::Blue,
+    NamedColor::Green,
+    NamedColor::Orange,
+    NamedColor::Purple,
+    NamedColor::Brown,
+];
+
+/// Renders the decision regions of a classifier with two input features.
+#[derive(Config, Debug)]
+pub struct DecisionBoundaryConfig {
+    /// Number of grid points along each axis, at least 2.
+    #[config(default = 200)]
+    pub resolution: usize,
+    /// Margin added around the bounding box of the points, in data units.
+    #[config(default = 0.5)]
+    pub padding: f32,
+    #[config(default = 0.6)]
+    pub opacity: f64,
+}
+
+impl DecisionBoundaryConfig {
+    /// Evaluates `classifier` on a meshgrid covering `points` and plots its predictions
+    /// below the points. Binary classifiers show the probability of class 1. Others
+    /// show the most probable class of each grid point, the probability of each class
+    /// being a separate trace hidden until selected in the legend.
+    ///
+    /// The classifier maps a `[n, 2]` batch of points to `[n, num_classes]` logits.
+    pub fn plot<B, F>(
+        &self,
+        classifier: F,
+        points: &[[f32; 2]],
+        labels: &[usize],
+        device: &B::Device,
+    ) -> Plot
+    where
+        B: Backend,
+        F: Fn(Tensor<B, 2>) -> Tensor<B, 2>,
+    {
+        let (x1, x2) = self.grid(points);
+        let n = self.resolution;
+
+        let grid: Vec<f32> = x2
+            .iter()
+            .flat_map(|x2| x1.iter().flat_map(move |x1| [*x1, *x2]))
+            .collect();
+        let grid =
+            Tensor::<B, 2>::from_data(Data::new(grid, Shape::new([n * n, 2])).convert(), device);
+
+        let probabilities = softmax(classifier(grid), 1);
+        let [_, num_classes] = probabilities.dims();
+
+        let mut plot = Plot::new();
+
+        if num_classes == 2 {
+            let z = rows(probabilities.slice([0..n * n, 1..2]), n);
+            plot.add_trace(
+                Contour::new(x1, x2, z)
+                    .name("P(class 1)")
+                    .color_scale(ColorScale::Palette(ColorScalePalette::RdBu))
+                    .reverse_scale(true)
+                    .opacity(self.opacity),
+            );
+        } else {
+            let z = rows(probabilities.clone().argmax(1).float(), n);
+            plot.add_trace(
+                HeatMap::new(x1.clone(), x2.clone(), z)
+                    .name("Predicted class")
+                    .color_scale(ColorScale::Palette(ColorScalePalette::Portland))
+                    .opacity(self.opacity),
+            );
+
+            for class in 0..num_classes {
+                let z = rows(probabilities.clone().slice([0..n * n, class..class + 1]), n);
+                plot.add_trace(
+                    Contour::new(x1.clone(), x2.clone(), z)
+                        .name(&format!("P(class {class})"))
+                        .visible(Visible::LegendOnly)
+                        .show_legend(true)
+                        .show_scale(false)
+                        .opacity(self.opacity),
+                );
+            }
+        }
+
+        let num_labels = labels.iter().max().map_or(0, |max| max + 1);
+        for class in 0..num_labels {
+            let (x1, x2): (Vec<f32>, Vec<f32>) = points
+                .iter()
+                .zip(labels)
+                .filter(|(_, label)| **label == class)
+                .map(|(point, _)| (point[0], point[1]))
+                .unzip();
+
+            plot.add_trace(
+                Scatter::new(x1, x2)
+                    .name(format!("Class {class}"))
+                    .mode(Mode::Markers)
+                    .marker(Marker::new().color(CLASS_COLORS[class % CLASS_COLORS.len()])),
+            );
+        }
+
+        plot
+    }
+
+    /// Same as [`plot`](Self::plot), written as `decision_boundary.html` in the
+    /// artifact directory next to `model.html`.
+    pub fn write<B, F>(
+        &self,
+        artifact_dir: &str,
+        classifier: F,
+        points: &[[f32; 2]],
+        labels: &[usize],
+        device: &B::Device,
+    ) where
+        B: Backend,
+        F: Fn(Tensor<B, 2>) -> Tensor<B, 2>,
+    {
+        let mut plot = self.plot::<B, F>(classifier, points, labels, device);
+        plot.use_local_plotly();
+        plot.write_html(format!("{artifact_dir}/decision_boundary.html"));
+    }
+
+    /// Evenly spaced coordinates of the grid along each feature, covering the bounding
+    /// box of `points` with the padding, or `[-1, 1]` without points.
+    pub fn grid(&self, points: &[[f32; 2]]) -> (Vec<f32>, Vec<f32>) {
+        assert!(
+            self.resolution >= 2,
+            "The grid should have at least 2 points per axis, got a resolution of {}",
+            self.resolution
+        );
+
+        let axis = |i: usize| -> Vec<f32> {
+            let min = points.iter().map(|p| p[i]).fold(f32::INFINITY, f32::min);
+            let max = points
+                .iter()
+                .map(|p| p[i])
+                .fold(f32::NEG_INFINITY, f32::max);
+            let (start, end) = if points.is_empty() {
+                (-1.0, 1.0)
+            } else {
+                (min - self.padding, max + self.padding)
+            };
+            let step = (end - start) / (self.resolution - 1) as f32;
+
+            (0..self.resolution)
+                .map(|k| start + k as f32 * step)
+                .collect()
+        };
+
+        (axis(0), axis(1))
+    }
+}
+
+// Reshapes the values of a flattened grid into rows of the second feature
+fn rows<B: Backend, const D: usize>(values: Tensor<B, D>, n: usize) -> Vec<Vec<f32>> {
+    values
+        .into_data()
+        .convert::<f32>()
+        .value
+        .chunks(n)
+        .map(|row| row.to_vec())
+        .collect()
+}
@@ -0,0 +1,95 @@
+use burn::config::Config;
+use burn::data::dataset::Dataset;
+use ndarray_rand::rand::{self, Rng, SeedableRng};
+use ndarray_rand::rand_distr::{Distribution, Normal};
+
+use crate::split::{Split, SplitConfig};
+
+/// Points drawn uniformly from `[-1, 1]²` and labelled by the parity of the cell they
+/// fall in. With two cells per side this is the XOR problem, more cells give a
+/// checkerboard.
+#[derive(Config, Debug)]
+pub struct XorConfig {
+    pub n_samples: usize,
+    #[config(default = 2)]
+    pub cells: usize,
+    /// Standard deviation of the noise added to the points after labelling them.
+    pub noise: Option<f64>,
+    #[config(default = 21)]
+    pub seed: u64,
+}
+
+impl XorConfig {
+    pub fn generate(&self) -> Vec<XorItem> {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(self.seed);
+        let normal = self
+            .noise
+            .map(|noise| Normal::<f64>::new(0.0, noise).unwrap());
+        let cell = |value: f64| ((value + 1.0) / 2.0 * self.cells as f64) as usize;
+
+        (0..self.n_samples)
+            .map(|_| {
+                let mut x: [f64; 2] = [rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0)];
+                let y = ((cell(x[0]) + cell(x[1])) % 2) as i8;
+
+                if let Some(normal) = normal {
+                    x.iter_mut()
+                        .for_each(|value| *value += normal.sample(&mut rng));
+                }
+
+                XorItem {
+                    x: [x[0] as f32, x[1] as f32],
+                    y,
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct XorItem {
+    pub x: [f32; 2],
+    pub y: i8,
+}
+
+pub struct XorDataset {
+    dataset: Vec<XorItem>,
+}
+
+impl Dataset<XorItem> for XorDataset {
+    fn get(&self, index: usize) -> Option<XorItem> {
+        self.dataset.get(index).cloned()
+    }
+
+    fn len(&self) -> usize {
+        self.dataset.len()
+    }
+}
+
+pub struct XorDatasetConfig {
+    pub xor: XorConfig,
+    pub split: SplitConfig,
+}
+
+impl XorDatasetConfig {
+    pub fn train(&self) -> XorDataset {
+        self.dataset(Split::Train)
+    }
+
+    pub fn valid(&self) -> XorDataset {
+        self.dataset(Split::Valid)
+    }
+
+    pub fn test(&self) -> XorDataset {
+        self.dataset(Split::Test)
+    }
+
+    fn dataset(&self, split: Split) -> XorDataset {
+        let items = self.xor.generate();
+        let splits = self.split.split_by(items, |item| item.y);
+
+        XorDataset {
+            dataset: splits.take(split),
+        }
+    }
+}
@@ -0,0 +1,190 @@
+use burn::backend::NdArray;
+use burn::data::dataloader::batcher::Batcher;
+use burn::data::dataset::vision::MnistItem;
+use inside_deep_learning_with_burn::augmentation::{Augmentation, AugmentationConfig};
+use inside_deep_learning_with_burn::mist_data::data::MnistBatcher;
+use inside_deep_learning_with_burn::transform::Image;
+
+fn all_augmentations() -> Vec<Augmentation> {
+    vec![
+        Augmentation::Translate { max_shift: 3 },
+        Augmentation::Rotate { max_degrees: 15.0 },
+        Augmentation::Erase {
+            probability: 1.0,
+            min_area: 0.02,
+            max_area: 0.2,
+        },
+        Augmentation::Elastic {
+            alpha: 8.0,
+            sigma: 3.0,
+        },
+        Augmentation::GaussianNoise { std: 10.0 },
+    ]
+}
+
+// Bright square in the middle of a dark 28x28 image, with a gradient inside
+fn digit(k: usize) -> [[f32; 28]; 28] {
+    let mut image = [[0.0; 28]; 28];
+    for (y, row) in image.iter_mut().enumerate().skip(8).take(12) {
+        for (x, pixel) in row.iter_mut().enumerate().skip(8).take(12) {
+            *pixel = ((y * 7 + x * 3 + k * 11) % 256) as f32;
+        }
+    }
+    image
+}
+
+fn augment_all(config: &AugmentationConfig) -> Vec<Image> {
+    let augmenter = config.init();
+    (0..8)
+        .map(|k| augmenter.apply(Image::from(&digit(k)), k, 0))
+        .collect()
+}
+
+#[test]
+fn same_seed_gives_same_images() {
+    let config = AugmentationConfig::new(all_augmentations()).with_seed(3);
+
+    assert_eq!(augment_all(&config), augment_all(&config));
+}
+
+#[test]
+fn different_seeds_give_different_images() {
+    let config = AugmentationConfig::new(all_augmentations());
+
+    assert_ne!(
+        augment_all(&config.clone().with_seed(1)),
+        augment_all(&config.with_seed(2))
+    );
+}
+
+#[test]
+fn augmentations_change_pixels_but_not_shape() {
+    for augmentation in all_augmentations() {
+        let config = AugmentationConfig::new(vec![augmentation.clone()]);
+        let original: Vec<Image> = (0..8).map(|k| Image::from(&digit(k))).collect();
+        let augmented = augment_all(&config);
+
+        assert!(augmented.iter().all(|image| image.shape() == [1, 28, 28]));
+        assert_ne!(augmented, original, "{augmentation} had no effect");
+    }
+}
+
+#[test]
+fn zero_strength_augmentations_are_identity() {
+    let config = AugmentationConfig::new(vec![
+        Augmentation::Translate { max_shift: 0 },
+        Augmentation::Rotate { max_degrees: 0.0 },
+        Augmentation::Erase {
+            probability: 0.0,
+            min_area: 0.1,
+            max_area: 0.2,
+        },
+    ]);
+    let original: Vec<Image> = (0..8).map(|k| Image::from(&digit(k))).collect();
+
+    assert_eq!(augment_all(&config), original);
+}
+
+fn items() -> Vec<MnistItem> {
+    (0..4)
+        .map(|k| MnistItem {
+            image: digit(k),
+            label: k as u8,
+        })
+        .collect()
+}
+
+#[test]
+fn batcher_without_augmentation_is_deterministic() {
+    let device = Default::default();
+    let batcher = MnistBatcher::<NdArray>::new(device);
+    let augmented = batcher
+        .clone()
+        .with_augmenter(AugmentationConfig::new(all_augmentations()).init());
+
+    let first = batcher.batch(items()).images.into_data();
+    let second = batcher.batch(items()).images.into_data();
+    let augmented = augmented.batch(items()).images.into_data();
+
+    assert_eq!(first.shape.dims, [4, 1, 28, 28]);
+    assert_eq!(first, second);
+    assert_ne!(first, augmented);
+}
+
+#[test]
+fn augmented_batches_only_depend_on_their_items() {
+    let config = AugmentationConfig::new(all_augmentations());
+    // Like two data loader workers, each with its own batcher
+    let first = MnistBatcher::<NdArray>::new(Default::default()).with_augmenter(config.init());
+    let second = MnistBatcher::<NdArray>::new(Default::default()).with_augmenter(config.init());
+
+    first.batch(items()[2..].to_vec());
+    let batch = first.batch(items()).images.into_data();
+
+    assert_eq!(batch, first.batch(items()).images.into_data());
+    assert_eq!(batch, second.batch(items()).images.into_data());
+}
+
+#[test]
+fn each_batch_gives_a_new_augmentation() {
+    let augmenter = AugmentationConfig::new(all_augmentations()).init();
+    let images = || (0..4).map(|k| Image::from(&digit(k))).collect::<Vec<_>>();
+    // The same items shuffled into another batch, as in the next epoch
+    let mut shuffled = images();
+    shuffled.swap(0, 3);
+
+    let first = augmenter.apply_batch(images());
+    let second = augmenter.apply_batch(shuffled);
+
+    assert_eq!(first.len(), 4);
+    assert_ne!(first[0], second[3]);
+    assert_ne!(first[3], second[0]);
+}
+
+#[test]
+#[should_panic(expected = "positive sigma")]
+fn elastic_rejects_a_zero_sigma() {
+    AugmentationConfig::new(vec![Augmentation::Elastic {
+        alpha: 8.0,
+        sigma: 0.0,
+    }])
+    .init();
+}
+
+#[test]
+fn invalid_parameters_are_rejected() {
+    let invalid = [
+        Augmentation::Rotate { max_degrees: -1.0 },
+        Augmentation::Erase {
+            probability: 1.5,
+            min_area: 0.1,
+            max_area: 0.2,
+        },
+        Augmentation::Erase {
+            probability: 0.5,
+            min_area: 0.3,
+            max_area: 0.2,
+        },
+        Augmentation::Erase {
+            probability: 0.5,
+            min_area: 0.1,
+            max_area: 1.5,
+        },
+        Augmentation::Elastic {
+            alpha: f32::NAN,
+            sigma: 3.0,
+        },
+        Augmentation::GaussianNoise { std: -1.0 },
+        Augmentation::GaussianNoise { std: f32::NAN },
+    ];
+
+    for augmentation in invalid {
+        let message = augmentation.to_string();
+        let config = AugmentationConfig::new(vec![augmentation]);
+
+        assert!(
+            std::panic::catch_unwind(|| config.init()).is_err(),
+            "{message} was accepted"
+        );
+    }
+}
@@ -0,0 +1,53 @@
+use inside_deep_learning_with_burn::blobs_data::data::{BlobsConfig, BlobsItem};
+
+fn keys(items: &[BlobsItem]) -> Vec<(u32, u32, i8)> {
+    items
+        .iter()
+        .map(|item| (item.x[0].to_bits(), item.x[1].to_bits(), item.y))
+        .collect()
+}
+
+#[test]
+fn spreads_the_samples_over_the_classes() {
+    let items = BlobsConfig::new(10, 3).generate();
+
+    let counts: Vec<usize> = (0..3)
+        .map(|class| items.iter().filter(|item| item.y == class).count())
+        .collect();
+
+    assert_eq!(items.len(), 10);
+    assert_eq!(counts, [4, 3, 3]);
+}
+
+#[test]
+fn points_are_drawn_around_the_center_of_their_class() {
+    let config = BlobsConfig::new(300, 4).with_cluster_std(0.1);
+    let centers = config.centers();
+
+    assert_eq!(centers.len(), 4);
+    for center in &centers {
+        assert!(center.iter().all(|value| (-10.0..10.0).contains(value)));
+    }
+    for item in config.generate() {
+        let [c1, c2] = centers[item.y as usize];
+        let distance = (item.x[0] as f64 - c1).hypot(item.x[1] as f64 - c2);
+        assert!(distance < 1.0, "{item:?} is {distance} from its center");
+    }
+}
+
+#[test]
+fn is_deterministic_for_a_seed() {
+    let config = BlobsConfig::new(50, 3);
+
+    assert_eq!(keys(&config.generate()), keys(&config.generate()));
+    assert_ne!(
+        keys(&config.generate()),
+        keys(&config.clone().with_seed(22).generate())
+    );
+}
+
+#[test]
+#[should_panic(expected = "classes")]
+fn zero_classes_panics() {
+    BlobsConfig::new(10, 0).generate();
+}
@@ -0,0 +1,142 @@
+use std::path::PathBuf;
+
+use burn::backend::NdArray;
+use burn::data::dataloader::batcher::Batcher;
+use burn::data::dataset::Dataset;
+use burn::optim::AdamConfig;
+use inside_deep_learning_with_burn::cifar_data::binary::{CifarDataset, CifarError};
+use inside_deep_learning_with_burn::cifar_data::data::{statistics, transform};
+use inside_deep_learning_with_burn::image_experiment::{ImageDataConfig, ImageDataset};
+use inside_deep_learning_with_burn::mist_data::data::MnistBatcher;
+use inside_deep_learning_with_burn::optimizer::OptimizerConfig;
+use inside_deep_learning_with_burn::split::Split;
+use inside_deep_learning_with_burn::train::TrainingConfig;
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/cifar");
+
+// Pixel values used when generating the fixture records, `i` indexes the red, green
+// then blue planes
+fn pixel(record: usize, i: usize) -> f32 {
+    ((record * 37 + i) % 256) as f32
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("cifar-{name}-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn reads_labels_and_channel_planes() {
+    let dataset = CifarDataset::test(FIXTURES).unwrap();
+
+    assert_eq!(dataset.len(), 2);
+    let labels: Vec<u8> = dataset.iter().map(|item| item.label).collect();
+    assert_eq!(labels, vec![3, 8]);
+
+    let item = dataset.get(1).unwrap();
+    assert_eq!(item.image.shape(), [3, 32, 32]);
+    assert_eq!(item.image.get(0, 0, 0), pixel(1, 0));
+    assert_eq!(item.image.get(0, 2, 5), pixel(1, 2 * 32 + 5));
+    assert_eq!(item.image.get(1, 0, 0), pixel(1, 1024));
+    assert_eq!(item.image.get(2, 31, 31), pixel(1, 3 * 1024 - 1));
+}
+
+#[test]
+fn batches_are_normalized_per_channel() {
+    let dataset = CifarDataset::test(FIXTURES).unwrap();
+    let batch = MnistBatcher::<NdArray>::new(Default::default())
+        .with_transform(transform())
+        .batch(dataset.iter().collect());
+
+    assert_eq!(batch.images.dims(), [2, 3, 32, 32]);
+    let targets: Vec<i64> = batch.targets.into_data().convert().value;
+    assert_eq!(targets, vec![3, 8]);
+
+    let statistics = statistics();
+    let images: Vec<f32> = batch.images.into_data().convert().value;
+    for (record, c, y, x) in [(0, 0, 0, 0), (0, 1, 4, 7), (1, 2, 31, 31), (1, 0, 16, 3)] {
+        let i = c * 1024 + y * 32 + x;
+        let expected = (pixel(record, i) / 255.0 - statistics.mean[c]) / statistics.std[c];
+        let found = images[record * 3 * 1024 + i];
+        assert!(
+            (found - expected).abs() < 1e-5,
+            "record {record} pixel ({c}, {y}, {x}): expected {expected}, found {found}"
+        );
+    }
+}
+
+#[test]
+fn missing_file_is_reported() {
+    assert!(matches!(
+        CifarDataset::train(FIXTURES),
+        Err(CifarError::MissingFile { .. })
+    ));
+}
+
+#[test]
+fn truncated_file_is_reported() {
+    let dir = temp_dir("truncated");
+    let bytes = std::fs::read(PathBuf::from(FIXTURES).join("test_batch.bin")).unwrap();
+    std::fs::write(dir.join("test_batch.bin"), &bytes[..bytes.len() - 1]).unwrap();
+
+    assert!(matches!(
+        CifarDataset::test(&dir),
+        Err(CifarError::Truncated { size: 6145, .. })
+    ));
+}
+
+#[test]
+fn bad_label_is_reported() {
+    let dir = temp_dir("label");
+    let mut bytes = std::fs::read(PathBuf::from(FIXTURES).join("test_batch.bin")).unwrap();
+    bytes[3073] = 10;
+    std::fs::write(dir.join("test_batch.bin"), bytes).unwrap();
+
+    assert!(matches!(
+        CifarDataset::test(&dir),
+        Err(CifarError::BadLabel {
+            index: 1,
+            label: 10,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn splits_are_read_by_name() {
+    assert_eq!(CifarDataset::split(FIXTURES, Split::Test).unwrap().len(), 2);
+    assert!(matches!(
+        CifarDataset::split(FIXTURES, Split::Train),
+        Err(CifarError::MissingFile { .. })
+    ));
+    assert!(matches!(
+        CifarDataset::split(FIXTURES, Split::Valid),
+        Err(CifarError::NoSplit {
+            split: Split::Valid
+        })
+    ));
+}
+
+#[test]
+fn image_experiments_train_on_cifar() {
+    let data = ImageDataConfig::new()
+        .with_dataset(ImageDataset::Cifar10)
+        .with_data_dir(Some(FIXTURES.to_string()));
+    let training = TrainingConfig::new(OptimizerConfig::Adam(AdamConfig::new())).with_batch_size(2);
+
+    assert_eq!(data.num_classes(), 10);
+    assert_eq!(data.image_shape(), [3, 32, 32]);
+
+    let batches: Vec<_> = data
+        .dataloader(
+            &training,
+            data.batcher::<NdArray>(Default::default()),
+            Split::Test,
+        )
+        .iter()
+        .collect();
+
+    assert_eq!(batches.len(), 1);
+    assert_eq!(batches[0].images.dims(), [2, 3, 32, 32]);
+}
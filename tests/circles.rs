@@ -0,0 +1,54 @@
+use inside_deep_learning_with_burn::circles_data::data::{CirclesConfig, CirclesItem};
+
+fn keys(items: &[CirclesItem]) -> Vec<(u32, u32, i8)> {
+    items
+        .iter()
+        .map(|item| (item.x[0].to_bits(), item.x[1].to_bits(), item.y))
+        .collect()
+}
+
+#[test]
+fn inner_circle_is_scaled_by_the_factor() {
+    let (x, y) = CirclesConfig::new(8, 6)
+        .with_shuffle(false)
+        .with_factor(0.5)
+        .arrays();
+
+    assert_eq!(x.shape(), &[14, 2]);
+    assert_eq!(y.iter().filter(|&&y| y == 0).count(), 8);
+    assert_eq!(y.iter().filter(|&&y| y == 1).count(), 6);
+    for (row, y) in x.rows().into_iter().zip(y) {
+        let radius = row[0].hypot(row[1]);
+        let expected = if y == 0 { 1.0 } else { 0.5 };
+        assert!((radius - expected).abs() < 1e-12, "radius was {radius}");
+    }
+}
+
+#[test]
+fn points_are_evenly_spaced_on_each_circle() {
+    let (x, _) = CirclesConfig::new(4, 0).with_shuffle(false).arrays();
+
+    let angles: Vec<f64> = x
+        .rows()
+        .into_iter()
+        .map(|row| row[1].atan2(row[0]))
+        .collect();
+    let expected = [0.0, 0.5, 1.0, -0.5].map(|turn| turn * std::f64::consts::PI);
+    for (angle, expected) in angles.iter().zip(expected) {
+        assert!(
+            (angle.abs() - expected.abs()).abs() < 1e-12,
+            "angle was {angle}"
+        );
+    }
+}
+
+#[test]
+fn is_deterministic_for_a_seed() {
+    let config = CirclesConfig::new(50, 50).with_noise(Some(0.05));
+
+    assert_eq!(keys(&config.generate()), keys(&config.generate()));
+    assert_ne!(
+        keys(&config.generate()),
+        keys(&config.clone().with_seed(22).generate())
+    );
+}
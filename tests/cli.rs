@@ -0,0 +1,293 @@
+mod common;
+
+use std::path::PathBuf;
+
+use burn::config::Config;
+use burn::optim::AdamConfig;
+use common::TempDir;
+use inside_deep_learning_with_burn::backend::BackendKind;
+use inside_deep_learning_with_burn::cli::{Cli, CliError, Command};
+use inside_deep_learning_with_burn::optimizer::OptimizerConfig;
+use inside_deep_learning_with_burn::overrides::{self, OverrideError};
+use inside_deep_learning_with_burn::scheduler::SchedulerConfig;
+use inside_deep_learning_with_burn::train::TrainingConfig;
+
+#[derive(Config)]
+struct ExperimentConfig {
+    hidden: Vec<usize>,
+    training: TrainingConfig,
+}
+
+fn config() -> ExperimentConfig {
+    ExperimentConfig::new(
+        vec![30, 30],
+        TrainingConfig::new(OptimizerConfig::Adam(AdamConfig::new())),
+    )
+}
+
+fn parse(args: &[&str]) -> Result<Cli, CliError> {
+    Cli::parse(args.iter().map(|arg| arg.to_string()))
+}
+
+fn set(overrides: &[(&str, &str)]) -> Result<ExperimentConfig, OverrideError> {
+    let overrides: Vec<(String, String)> = overrides
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+    overrides::apply(&config(), &overrides)
+}
+
+#[test]
+fn parses_the_command_and_its_options() {
+    let cli = parse(&[
+        "evaluate",
+        "--artifact-dir",
+        "runs",
+        "--run=20240517-093000-1a2b3c4d",
+        "--backend",
+        "ndarray",
+        "--config",
+        "config.json",
+        "--index",
+        "3",
+    ])
+    .unwrap();
+
+    assert_eq!(cli.command, Command::Evaluate);
+    assert_eq!(cli.artifact_dir.as_deref(), Some("runs"));
+    assert_eq!(cli.run.as_deref(), Some("20240517-093000-1a2b3c4d"));
+    assert_eq!(cli.backend, BackendKind::NdArray);
+    assert_eq!(cli.config, Some(PathBuf::from("config.json")));
+    assert_eq!(cli.index, Some(3));
+}
+
+#[test]
+fn defaults_to_training_then_inference() {
+    let cli = parse(&["--backend", "ndarray"]).unwrap();
+
+    assert_eq!(cli.command, Command::Run);
+    assert!(cli.overrides.is_empty());
+}
+
+#[test]
+fn keeps_the_overrides_and_shortcuts_in_order() {
+    let cli = parse(&[
+        "train",
+        "--backend=ndarray",
+        "--epochs",
+        "5",
+        "--set",
+        "training.optimizer.Adam.beta_1=0.8",
+        "--set=training.num_epochs=7",
+        "--learning-rate",
+        "0.01",
+    ])
+    .unwrap();
+
+    assert_eq!(
+        cli.overrides,
+        [
+            ("training.num_epochs", "5"),
+            ("training.optimizer.Adam.beta_1", "0.8"),
+            ("training.num_epochs", "7"),
+            ("training.learning_rate", "0.01"),
+        ]
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+    );
+}
+
+#[test]
+fn rejects_invalid_arguments() {
+    assert_eq!(parse(&["--help"]), Err(CliError::HelpRequested));
+    assert_eq!(
+        parse(&["fit"]),
+        Err(CliError::UnknownCommand("fit".to_string()))
+    );
+    assert_eq!(
+        parse(&["train", "infer"]),
+        Err(CliError::UnknownCommand("infer".to_string()))
+    );
+    assert_eq!(
+        parse(&["--verbose", "1"]),
+        Err(CliError::UnknownFlag("--verbose".to_string()))
+    );
+    assert_eq!(
+        parse(&["train", "--epochs"]),
+        Err(CliError::MissingValue {
+            flag: "--epochs".to_string()
+        })
+    );
+    assert!(matches!(
+        parse(&["--set", "training.num_epochs"]),
+        Err(CliError::InvalidValue { flag, .. }) if flag == "--set"
+    ));
+    assert!(matches!(
+        parse(&["--index", "first"]),
+        Err(CliError::InvalidValue { flag, .. }) if flag == "--index"
+    ));
+}
+
+#[test]
+fn unknown_backends_list_the_compiled_ones() {
+    let Err(CliError::InvalidValue { flag, message, .. }) = parse(&["--backend", "cuda"]) else {
+        panic!("cuda should not be a backend");
+    };
+
+    assert_eq!(flag, "--backend");
+    for backend in BackendKind::enabled() {
+        assert!(message.contains(&backend.to_string()), "{message}");
+    }
+}
+
+#[test]
+fn sets_nested_keys() {
+    let config = set(&[
+        ("training.num_epochs", "3"),
+        ("training.optimizer.Adam.beta_1", "0.8"),
+        ("training.early_stopping.metric", "Accuracy"),
+    ])
+    .unwrap();
+
+    assert_eq!(config.training.num_epochs, 3);
+    assert_eq!(config.training.early_stopping.metric, "Accuracy");
+    let beta_1 = overrides::get(
+        &overrides::to_value(&config),
+        "training.optimizer.Adam.beta_1",
+    )
+    .and_then(|value| value.as_f64())
+    .unwrap();
+    assert!((beta_1 - 0.8).abs() < 1e-6, "beta_1 was {beta_1}");
+}
+
+#[test]
+fn parses_values_as_json() {
+    let config = set(&[
+        ("hidden", "[64, 32, 16]"),
+        ("training.early_stopping.patience", "5"),
+        (
+            "training.scheduler",
+            r#"{"StepDecay": {"step_size": 2, "gamma": 0.5}}"#,
+        ),
+        ("training.optimizer", r#"{"Sgd": {}}"#),
+    ])
+    .unwrap();
+
+    assert_eq!(config.hidden, [64, 32, 16]);
+    assert_eq!(config.training.early_stopping.patience, Some(5));
+    assert!(matches!(
+        config.training.scheduler,
+        SchedulerConfig::StepDecay { step_size: 2, gamma } if gamma == 0.5
+    ));
+    assert!(matches!(config.training.optimizer, OptimizerConfig::Sgd(_)));
+}
+
+#[test]
+fn later_overrides_win() {
+    let config = set(&[("training.seed", "1"), ("training.seed", "2")]).unwrap();
+
+    assert_eq!(config.training.seed, 2);
+}
+
+#[test]
+fn rejects_unknown_keys_and_invalid_values() {
+    assert!(matches!(
+        set(&[("training.epochs", "3")]),
+        Err(OverrideError::UnknownKey { key }) if key == "training.epochs"
+    ));
+    assert!(matches!(
+        set(&[("hidden.width", "3")]),
+        Err(OverrideError::UnknownKey { .. })
+    ));
+    assert!(matches!(
+        set(&[("training.num_epochs", "many")]),
+        Err(OverrideError::InvalidValue { key, .. }) if key == "training.num_epochs"
+    ));
+}
+
+#[test]
+fn invalid_values_are_reported_against_their_key() {
+    assert!(matches!(
+        set(&[("training.seed", "1"), ("training.batch_size", "-8")]),
+        Err(OverrideError::InvalidValue { key, .. }) if key == "training.batch_size"
+    ));
+}
+
+fn merge(name: &str, contents: &str) -> Result<ExperimentConfig, OverrideError> {
+    let dir = TempDir::new(name);
+    let path = dir.join("config.json");
+    std::fs::write(&path, contents).unwrap();
+
+    overrides::merge_file(&config(), path)
+}
+
+#[test]
+fn config_files_reject_unknown_keys() {
+    assert!(matches!(
+        merge("cli-typo", r#"{"training": {"epoch": 4}}"#),
+        Err(OverrideError::UnknownKey { key }) if key == "training.epoch"
+    ));
+    assert!(matches!(
+        merge("cli-top-level", r#"{"hiden": [8]}"#),
+        Err(OverrideError::UnknownKey { key }) if key == "hiden"
+    ));
+    assert!(matches!(
+        merge(
+            "cli-nested",
+            r#"{"training": {"early_stopping": {"patient": 3}}}"#
+        ),
+        Err(OverrideError::UnknownKey { key }) if key == "training.early_stopping.patient"
+    ));
+}
+
+#[test]
+fn config_files_switch_enum_variants() {
+    let config = merge(
+        "cli-variant",
+        r#"{"training": {"optimizer": {"Sgd": {}}, "scheduler": {"Exponential": {"gamma": 0.9}}}}"#,
+    )
+    .unwrap();
+
+    assert!(matches!(config.training.optimizer, OptimizerConfig::Sgd(_)));
+    assert!(matches!(
+        config.training.scheduler,
+        SchedulerConfig::Exponential { gamma } if gamma == 0.9
+    ));
+}
+
+#[test]
+fn applies_the_config_file_before_the_overrides() {
+    let path = std::env::temp_dir().join(format!("cli-test-{}.json", std::process::id()));
+    std::fs::write(
+        &path,
+        r#"{"hidden": [8], "training": {"num_epochs": 4, "batch_size": 16}}"#,
+    )
+    .unwrap();
+
+    let cli = parse(&[
+        "--backend",
+        "ndarray",
+        "--config",
+        path.to_str().unwrap(),
+        "--batch-size",
+        "8",
+    ])
+    .unwrap();
+    let config = cli.apply(config());
+    std::fs::remove_file(&path).unwrap();
+    let config = config.unwrap();
+
+    assert_eq!(config.hidden, [8]);
+    assert_eq!(config.training.num_epochs, 4);
+    assert_eq!(config.training.batch_size, 8);
+    assert_eq!(config.training.seed, 42);
+}
+
+#[test]
+fn default_backend_is_the_first_enabled() {
+    assert_eq!(
+        BackendKind::default_kind(),
+        BackendKind::enabled().first().copied()
+    );
+    #[cfg(feature = "ndarray")]
+    assert_eq!(BackendKind::default_kind(), Some(BackendKind::NdArray));
+}
@@ -0,0 +1,234 @@
+use std::path::PathBuf;
+
+use burn::data::dataset::Dataset;
+use inside_deep_learning_with_burn::csv_data::data::{
+    CsvConfig, CsvDataset, CsvError, CsvSplits, MissingValues, TargetKind,
+};
+use inside_deep_learning_with_burn::split::SplitConfig;
+
+// Rows 0 to 19 have every value, row 20 misses `a`, row 21 misses `b` and the last
+// row misses its target. The target is the row index, so each item can be traced
+// back to its raw features.
+fn write_csv(name: &str) -> PathBuf {
+    let mut csv = String::from("a,b,y\n");
+    for id in 0..20 {
+        csv.push_str(&format!("{},{},{id}\n", raw_a(id), raw_b(id)));
+    }
+    csv.push_str("na,6,20\n21,,21\n5,5,\n");
+
+    let path = std::env::temp_dir().join(format!("csv-{name}-{}.csv", std::process::id()));
+    std::fs::write(&path, csv).unwrap();
+    path
+}
+
+fn raw_a(id: usize) -> f32 {
+    id as f32
+}
+
+fn raw_b(id: usize) -> f32 {
+    ((id * 3) % 7) as f32
+}
+
+fn load(name: &str, missing: MissingValues, standardize: bool) -> CsvSplits {
+    let path = write_csv(name);
+    let config = CsvConfig::new(
+        path.to_str().unwrap().to_string(),
+        vec!["a".to_string(), "b".to_string()],
+        "y".to_string(),
+    )
+    .with_missing(missing)
+    .with_standardize(standardize);
+    let split = SplitConfig {
+        train: 0.6,
+        valid: 0.2,
+        seed: 7,
+        stratify: false,
+    };
+
+    let splits = config.load(&split).unwrap();
+    std::fs::remove_file(path).unwrap();
+    splits
+}
+
+fn ids(dataset: &CsvDataset) -> Vec<usize> {
+    dataset.iter().map(|item| item.y as usize).collect()
+}
+
+fn all_ids(splits: &CsvSplits) -> Vec<usize> {
+    let mut ids: Vec<usize> = [&splits.train, &splits.valid, &splits.test]
+        .into_iter()
+        .flat_map(ids)
+        .collect();
+    ids.sort();
+    ids
+}
+
+fn mean_std(values: &[f32]) -> (f32, f32) {
+    let n = values.len() as f32;
+    let mean = values.iter().sum::<f32>() / n;
+    let variance = values
+        .iter()
+        .map(|value| (value - mean).powi(2))
+        .sum::<f32>()
+        / n;
+    (mean, variance.sqrt())
+}
+
+fn assert_close(found: f32, expected: f32, what: &str) {
+    assert!(
+        (found - expected).abs() < 1e-4,
+        "{what}: expected {expected}, found {found}"
+    );
+}
+
+#[test]
+fn drops_rows_with_missing_values() {
+    let splits = load("drop", MissingValues::Drop, false);
+
+    assert_eq!(all_ids(&splits), (0..20).collect::<Vec<_>>());
+    for item in splits
+        .train
+        .iter()
+        .chain(splits.valid.iter())
+        .chain(splits.test.iter())
+    {
+        let id = item.y as usize;
+        assert_eq!(item.x, [raw_a(id), raw_b(id)]);
+    }
+}
+
+#[test]
+fn fills_missing_values() {
+    let splits = load("fill", MissingValues::Fill { value: -1.0 }, false);
+
+    // Only the row without a target is dropped
+    assert_eq!(all_ids(&splits), (0..22).collect::<Vec<_>>());
+    for item in splits
+        .train
+        .iter()
+        .chain(splits.valid.iter())
+        .chain(splits.test.iter())
+    {
+        let expected = match item.y as usize {
+            20 => [-1.0, 6.0],
+            21 => [21.0, -1.0],
+            id => [raw_a(id), raw_b(id)],
+        };
+        assert_eq!(item.x, expected);
+    }
+}
+
+#[test]
+fn fills_missing_values_with_the_training_mean() {
+    let splits = load("mean", MissingValues::Mean, false);
+    let train: Vec<_> = splits.train.iter().collect();
+    let mean = |i: usize| {
+        let values: Vec<f32> = train
+            .iter()
+            .filter(|item| !matches!((item.y as usize, i), (20, 0) | (21, 1)))
+            .map(|item| item.x[i])
+            .collect();
+        mean_std(&values).0
+    };
+
+    assert_eq!(all_ids(&splits), (0..22).collect::<Vec<_>>());
+    assert_close(splits.preprocessing.mean[0], mean(0), "mean of a");
+    assert_close(splits.preprocessing.mean[1], mean(1), "mean of b");
+    for item in splits
+        .train
+        .iter()
+        .chain(splits.valid.iter())
+        .chain(splits.test.iter())
+    {
+        match item.y as usize {
+            20 => assert_close(item.x[0], mean(0), "missing a"),
+            21 => assert_close(item.x[1], mean(1), "missing b"),
+            _ => {}
+        }
+    }
+}
+
+#[test]
+fn standardizes_every_split_with_the_training_statistics() {
+    let splits = load("standardize", MissingValues::Drop, true);
+    let train = ids(&splits.train);
+    let a: Vec<f32> = train.iter().map(|&id| raw_a(id)).collect();
+    let b: Vec<f32> = train.iter().map(|&id| raw_b(id)).collect();
+    let (mean_a, std_a) = mean_std(&a);
+    let (mean_b, std_b) = mean_std(&b);
+
+    assert_close(splits.preprocessing.mean[0], mean_a, "mean of a");
+    assert_close(splits.preprocessing.std[0], std_a, "std of a");
+    assert_close(splits.preprocessing.mean[1], mean_b, "mean of b");
+    assert_close(splits.preprocessing.std[1], std_b, "std of b");
+
+    // The statistics of the whole file differ, so the check below would fail if the
+    // valid or test rows were standardized with them or with their own
+    let everything: Vec<f32> = (0..20).map(raw_a).collect();
+    assert!((mean_std(&everything).0 - mean_a).abs() > 1e-3);
+
+    for dataset in [&splits.train, &splits.valid, &splits.test] {
+        assert!(!dataset.is_empty());
+        for item in dataset.iter() {
+            let id = item.y as usize;
+            assert_close(item.x[0], (raw_a(id) - mean_a) / std_a, "a");
+            assert_close(item.x[1], (raw_b(id) - mean_b) / std_b, "b");
+        }
+    }
+}
+
+#[test]
+fn classification_targets_are_sorted_class_indices() {
+    let path = std::env::temp_dir().join(format!("csv-classes-{}.csv", std::process::id()));
+    std::fs::write(&path, "a,class\n1,dog\n2,cat\n3,dog\n4,bird\n").unwrap();
+    let config = CsvConfig::new(
+        path.to_str().unwrap().to_string(),
+        vec!["a".to_string()],
+        "class".to_string(),
+    )
+    .with_kind(TargetKind::Classification)
+    .with_standardize(false);
+    let split = SplitConfig {
+        train: 1.0,
+        valid: 0.0,
+        seed: 0,
+        stratify: false,
+    };
+
+    let splits = config.load(&split).unwrap();
+    std::fs::remove_file(path).unwrap();
+
+    assert_eq!(splits.preprocessing.classes, ["bird", "cat", "dog"]);
+    let mut items: Vec<(f32, f32)> = splits
+        .train
+        .iter()
+        .map(|item| (item.x[0], item.y))
+        .collect();
+    items.sort_by(|a, b| a.0.total_cmp(&b.0));
+    assert_eq!(items, [(1.0, 2.0), (2.0, 1.0), (3.0, 2.0), (4.0, 0.0)]);
+}
+
+#[test]
+fn invalid_values_are_reported() {
+    let path = std::env::temp_dir().join(format!("csv-invalid-{}.csv", std::process::id()));
+    std::fs::write(&path, "a,y\n1,2\nabc,3\n").unwrap();
+    let config = CsvConfig::new(
+        path.to_str().unwrap().to_string(),
+        vec!["a".to_string()],
+        "y".to_string(),
+    );
+    let split = SplitConfig {
+        train: 1.0,
+        valid: 0.0,
+        seed: 0,
+        stratify: false,
+    };
+
+    let result = config.load(&split);
+    std::fs::remove_file(path).unwrap();
+
+    assert!(matches!(
+        result,
+        Err(CsvError::InvalidValue { line: 3, column, .. }) if column == "a"
+    ));
+}
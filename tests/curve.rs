@@ -0,0 +1,78 @@
+use inside_deep_learning_with_burn::curve_data::data::{CurveConfig, CurveFunction, CurveItem};
+
+fn keys(items: &[CurveItem]) -> Vec<(u32, u32)> {
+    items
+        .iter()
+        .map(|item| (item.x.to_bits(), item.y.to_bits()))
+        .collect()
+}
+
+fn polynomial() -> CurveFunction {
+    // 1 - 2x + x²
+    CurveFunction::Polynomial {
+        coefficients: vec![1.0, -2.0, 1.0],
+    }
+}
+
+#[test]
+fn inputs_are_evenly_spaced() {
+    let items = CurveConfig::new(polynomial(), -1.0, 1.0, 5).generate();
+
+    let x: Vec<f32> = items.iter().map(|item| item.x).collect();
+    assert_eq!(x, [-1.0, -0.5, 0.0, 0.5, 1.0]);
+}
+
+#[test]
+fn follows_the_function_without_noise() {
+    let items = CurveConfig::new(polynomial(), -2.0, 3.0, 11)
+        .with_noise_std(0.0)
+        .generate();
+
+    for item in items {
+        let expected = (item.x - 1.0).powi(2);
+        assert!((item.y - expected).abs() < 1e-5, "{item:?}");
+    }
+
+    let sinusoid = CurveFunction::Sinusoid {
+        amplitude: 2.0,
+        frequency: 3.0,
+        phase: 0.5,
+    };
+    assert!((sinusoid.eval(1.0) - 2.0 * 3.5f64.sin()).abs() < 1e-12);
+}
+
+#[test]
+fn noise_grows_with_the_slope() {
+    let config = CurveConfig::new(polynomial(), -1.0, 1.0, 4000)
+        .with_noise_std(0.0)
+        .with_noise_slope(0.5);
+    let clean = CurveConfig::new(polynomial(), -1.0, 1.0, 4000).with_noise_std(0.0);
+
+    let residuals: Vec<(f32, f32)> = config
+        .generate()
+        .iter()
+        .zip(clean.generate())
+        .map(|(noisy, clean)| (noisy.x, noisy.y - clean.y))
+        .collect();
+    let spread = |inner: bool| {
+        let values: Vec<f32> = residuals
+            .iter()
+            .filter(|(x, _)| (x.abs() < 0.5) == inner)
+            .map(|(_, residual)| residual.abs())
+            .collect();
+        values.iter().sum::<f32>() / values.len() as f32
+    };
+
+    assert!(spread(false) > 2.0 * spread(true));
+}
+
+#[test]
+fn is_deterministic_for_a_seed() {
+    let config = CurveConfig::new(polynomial(), 0.0, 1.0, 100);
+
+    assert_eq!(keys(&config.generate()), keys(&config.generate()));
+    assert_ne!(
+        keys(&config.generate()),
+        keys(&config.clone().with_seed(22).generate())
+    );
+}
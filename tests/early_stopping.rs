@@ -0,0 +1,184 @@
+use burn::train::checkpoint::CheckpointingAction::{Delete, Save};
+use inside_deep_learning_with_burn::early_stopping::{EarlyStoppingConfig, Mode};
+use inside_deep_learning_with_burn::history::EpochValue;
+
+fn epochs(values: &[f64]) -> Vec<EpochValue> {
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, &value)| EpochValue {
+            epoch: i + 1,
+            value,
+        })
+        .collect()
+}
+
+fn best_epoch(config: &EarlyStoppingConfig, values: &[f64]) -> Option<usize> {
+    config.best(&epochs(values)).map(|best| best.epoch)
+}
+
+#[test]
+fn min_improves_by_going_down() {
+    let config = EarlyStoppingConfig::new();
+
+    assert!(config.improves(0.5, None));
+    assert!(config.improves(0.4, Some(0.5)));
+    assert!(!config.improves(0.5, Some(0.5)));
+    assert!(!config.improves(0.6, Some(0.5)));
+}
+
+#[test]
+fn max_improves_by_going_up() {
+    let config = EarlyStoppingConfig::new().with_mode(Some(Mode::Max));
+
+    assert!(config.improves(0.5, None));
+    assert!(config.improves(0.6, Some(0.5)));
+    assert!(!config.improves(0.5, Some(0.5)));
+    assert!(!config.improves(0.4, Some(0.5)));
+}
+
+#[test]
+fn improvements_need_more_than_min_delta() {
+    let min = EarlyStoppingConfig::new().with_min_delta(0.1);
+    let max = EarlyStoppingConfig::new()
+        .with_mode(Some(Mode::Max))
+        .with_min_delta(0.1);
+
+    assert!(!min.improves(0.45, Some(0.5)));
+    assert!(min.improves(0.35, Some(0.5)));
+    assert!(!max.improves(0.55, Some(0.5)));
+    assert!(max.improves(0.65, Some(0.5)));
+}
+
+#[test]
+fn nan_never_improves() {
+    let config = EarlyStoppingConfig::new();
+
+    assert!(!config.improves(f64::NAN, None));
+    assert!(!config.improves(f64::NAN, Some(0.5)));
+}
+
+#[test]
+fn best_follows_the_mode() {
+    let values = [0.9, 0.5, 0.7, 0.3, 0.8];
+
+    assert_eq!(best_epoch(&EarlyStoppingConfig::new(), &values), Some(4));
+    assert_eq!(
+        best_epoch(
+            &EarlyStoppingConfig::new().with_mode(Some(Mode::Max)),
+            &values
+        ),
+        Some(1)
+    );
+    assert_eq!(best_epoch(&EarlyStoppingConfig::new(), &[]), None);
+}
+
+#[test]
+fn best_keeps_the_first_value_within_min_delta() {
+    let values = [1.0, 0.95, 0.92, 0.85, 0.84];
+    let config = EarlyStoppingConfig::new().with_min_delta(0.1);
+
+    // 0.95 and 0.92 are within 0.1 of 1.0, 0.85 is not, then 0.84 is within 0.1 of it
+    assert_eq!(best_epoch(&config, &values), Some(4));
+    assert_eq!(best_epoch(&EarlyStoppingConfig::new(), &values), Some(5));
+}
+
+#[test]
+fn best_skips_leading_nan() {
+    let values = [f64::NAN, 0.6, f64::NAN, 0.4];
+
+    let best = EarlyStoppingConfig::new().best(&epochs(&values)).unwrap();
+    assert_eq!(
+        best,
+        EpochValue {
+            epoch: 4,
+            value: 0.4
+        }
+    );
+}
+
+#[test]
+fn registered_metrics_are_accepted() {
+    EarlyStoppingConfig::new().check_metric(&["Accuracy", "Loss"]);
+    EarlyStoppingConfig::new()
+        .with_metric("Accuracy".to_string())
+        .check_metric(&["Accuracy", "Loss"]);
+}
+
+#[test]
+#[should_panic(expected = "Unknown early stopping metric accuracy")]
+fn unknown_metrics_are_rejected() {
+    EarlyStoppingConfig::new()
+        .with_metric("accuracy".to_string())
+        .check_metric(&["Accuracy", "Loss"]);
+}
+
+#[test]
+fn mode_follows_the_metric_unless_set() {
+    let accuracy = EarlyStoppingConfig::new().with_metric("Accuracy".to_string());
+
+    assert_eq!(EarlyStoppingConfig::new().mode(), Mode::Min);
+    assert_eq!(accuracy.mode(), Mode::Max);
+    assert!(accuracy.improves(0.9, Some(0.8)));
+    assert_eq!(
+        EarlyStoppingConfig::new()
+            .with_metric("Perplexity".to_string())
+            .mode(),
+        Mode::Min
+    );
+    assert_eq!(accuracy.with_mode(Some(Mode::Max)).mode(), Mode::Max);
+}
+
+#[test]
+#[should_panic(expected = "Early stopping metric Accuracy improves with mode Max, not Min")]
+fn mode_against_the_metric_is_rejected() {
+    EarlyStoppingConfig::new()
+        .with_metric("Accuracy".to_string())
+        .with_mode(Some(Mode::Min))
+        .check_metric(&["Accuracy", "Loss"]);
+}
+
+#[test]
+fn early_stopping_waits_for_patience_epochs_after_the_best() {
+    let mut strategy = EarlyStoppingConfig::new()
+        .with_patience(Some(2))
+        .early_stopping()
+        .unwrap();
+
+    // Nothing recorded yet, e.g. the validation metric of a resumed run
+    assert!(!strategy.update(1, None));
+    assert!(!strategy.update(2, None));
+    assert!(!strategy.update(3, Some(0.5)));
+    assert!(!strategy.update(4, Some(0.6)));
+    assert!(strategy.update(5, Some(0.5)));
+}
+
+#[test]
+fn checkpointing_keeps_the_best_and_the_last_epochs() {
+    let mut strategy = EarlyStoppingConfig::new().checkpointing();
+
+    assert_eq!(strategy.update(1, Some(0.5)), [Save]);
+    // Epoch 2 is both the best and the last
+    assert_eq!(strategy.update(2, Some(0.4)), [Delete(1), Save]);
+    assert_eq!(strategy.update(3, Some(0.6)), [Save]);
+    // Epoch 3 is neither anymore, epoch 2 stays the best
+    assert_eq!(strategy.update(4, Some(0.7)), [Delete(3), Save]);
+    assert_eq!(strategy.update(5, Some(0.3)), [Delete(2), Delete(4), Save]);
+    assert_eq!(strategy.update(6, None), [Save]);
+    assert_eq!(strategy.update(7, Some(0.35)), [Delete(6), Save]);
+    assert_eq!(strategy.update(8, Some(0.35)), [Delete(7), Save]);
+}
+
+#[test]
+fn resumed_checkpointing_keeps_the_best_epoch_before_resuming() {
+    let best = EpochValue {
+        epoch: 2,
+        value: 0.4,
+    };
+    let mut strategy = EarlyStoppingConfig::new()
+        .checkpointing()
+        .resumed(Some(best), 4);
+
+    assert_eq!(strategy.update(5, Some(0.5)), [Delete(4), Save]);
+    assert_eq!(strategy.update(6, Some(0.3)), [Delete(2), Delete(5), Save]);
+}
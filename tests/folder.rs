@@ -0,0 +1,42 @@
+mod common;
+
+use burn::data::dataset::Dataset;
+use common::TempDir;
+use image::{GrayImage, Luma};
+use inside_deep_learning_with_burn::folder_data::data::{FolderError, ImageFolderConfig};
+use inside_deep_learning_with_burn::split::Split;
+
+fn write_image(dir: &TempDir, path: &str, value: u8) {
+    let path = dir.join(path);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    GrayImage::from_pixel(4, 4, Luma([value]))
+        .save(path)
+        .unwrap();
+}
+
+#[test]
+fn splits_are_read_from_their_directory() {
+    let dir = TempDir::new("folder-splits");
+    write_image(&dir, "train/cat/1.png", 10);
+    write_image(&dir, "train/ant/1.png", 20);
+    write_image(&dir, "train/ant/2.png", 30);
+    write_image(&dir, "test/cat/1.png", 40);
+    let config = ImageFolderConfig::new(dir.to_str().to_string(), 2, 2);
+
+    let train = config.split(Split::Train).unwrap();
+    assert_eq!(train.len(), 3);
+    assert_eq!(train.classes(), ["ant", "cat"]);
+    let labels: Vec<usize> = train.iter().map(|item| item.label).collect();
+    assert_eq!(labels, [0, 0, 1]);
+
+    let test = config.split(Split::Test).unwrap();
+    assert_eq!(test.len(), 1);
+    assert_eq!(test.get(0).unwrap().image.shape(), [1, 2, 2]);
+    assert_eq!(test.get(0).unwrap().image.get(0, 1, 1), 40.0);
+
+    assert_eq!(config.classes(Split::Train).unwrap(), ["ant", "cat"]);
+    assert!(matches!(
+        config.split(Split::Valid),
+        Err(FolderError::Io { path, .. }) if path.ends_with("valid")
+    ));
+}
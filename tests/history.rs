@@ -0,0 +1,116 @@
+mod common;
+
+use common::TempDir;
+use inside_deep_learning_with_burn::history::{EpochValue, HistoryError, TrainingHistory};
+use serde_json::Value;
+
+fn write_log(dir: &TempDir, path: &str, contents: &str) {
+    let path = dir.join(path);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, contents).unwrap();
+}
+
+// Two epochs of training logs and one of validation, laid out like burn's file logger
+fn artifact_dir(name: &str) -> TempDir {
+    let dir = TempDir::new(name);
+    write_log(&dir, "train/epoch-1/Loss.log", "1.0,2\n0.25,6\n");
+    write_log(&dir, "train/epoch-2/Loss.log", "0.5\n0.3\n\n");
+    write_log(&dir, "train/epoch-1/Learning_Rate.log", "0.1\n");
+    write_log(&dir, "train/epoch-2/Learning_Rate.log", "0.05\n");
+    write_log(&dir, "valid/epoch-1/Loss.log", "0.75,3\n0.25,1\n");
+    // Neither an epoch nor a log
+    write_log(&dir, "train/epoch-1/notes.txt", "1.0\n");
+    write_log(&dir, "train/checkpoints/Loss.log", "1.0\n");
+    dir
+}
+
+fn values(history: &TrainingHistory, split: &str, metric: &str) -> Vec<(usize, f64)> {
+    history
+        .get(split, metric)
+        .unwrap()
+        .epochs
+        .iter()
+        .map(|&EpochValue { epoch, value }| (epoch, value))
+        .collect()
+}
+
+#[test]
+fn epoch_values_are_the_means_weighted_by_the_counts() {
+    let dir = artifact_dir("history-means");
+
+    let history = TrainingHistory::load(dir.path()).unwrap();
+
+    // (1.0 * 2 + 0.25 * 6) / 8 and (0.5 + 0.3) / 2
+    assert_eq!(values(&history, "train", "Loss"), [(1, 0.4375), (2, 0.4)]);
+    // (0.75 * 3 + 0.25) / 4
+    assert_eq!(values(&history, "valid", "Loss"), [(1, 0.625)]);
+    assert_eq!(
+        values(&history, "train", "Learning Rate"),
+        [(1, 0.1), (2, 0.05)]
+    );
+    assert_eq!(history.metric_names(), ["Learning Rate", "Loss"]);
+}
+
+#[test]
+fn history_is_exported_to_csv_and_json() {
+    let dir = artifact_dir("history-export");
+    let history = TrainingHistory::load(dir.path()).unwrap();
+
+    assert_eq!(
+        history.to_csv(),
+        "split,metric,epoch,value\n\
+         train,Learning Rate,1,0.1\n\
+         train,Learning Rate,2,0.05\n\
+         train,Loss,1,0.4375\n\
+         train,Loss,2,0.4\n\
+         valid,Loss,1,0.625\n"
+    );
+
+    let json: Value = serde_json::from_str(&history.to_json()).unwrap();
+    let metrics = json["metrics"].as_array().unwrap();
+    assert_eq!(metrics.len(), 3);
+    assert_eq!(metrics[2]["split"], "valid");
+    assert_eq!(metrics[2]["metric"], "Loss");
+    assert_eq!(metrics[2]["epochs"][0]["epoch"], 1);
+    assert_eq!(metrics[2]["epochs"][0]["value"], 0.625);
+
+    history.write(dir.path()).unwrap();
+    for file in ["history.html", "history.csv", "history.json"] {
+        assert!(dir.join(file).is_file(), "{file}");
+    }
+}
+
+#[test]
+fn plot_has_a_subplot_per_metric() {
+    let dir = artifact_dir("history-plot");
+    let history = TrainingHistory::load(dir.path()).unwrap();
+
+    let plot = history.plot();
+
+    assert_eq!(plot.data().len(), 3);
+    let json = plot.to_json();
+    assert!(json.contains("Loss (valid)"));
+    assert!(json.contains("\"yaxis\":\"y2\""));
+    assert!(!json.contains("\"yaxis\":\"y3\""));
+}
+
+#[test]
+fn invalid_entries_are_reported() {
+    let dir = TempDir::new("history-invalid");
+    write_log(&dir, "train/epoch-1/Loss.log", "0.5\n0.4,two\n");
+
+    assert!(matches!(
+        TrainingHistory::load(dir.path()),
+        Err(HistoryError::InvalidEntry { line: 2, entry, .. }) if entry == "0.4,two"
+    ));
+}
+
+#[test]
+fn missing_logs_are_reported() {
+    let dir = TempDir::new("history-missing");
+
+    assert!(matches!(
+        TrainingHistory::load(dir.path()),
+        Err(HistoryError::MissingLogs { .. })
+    ));
+}
@@ -0,0 +1,103 @@
+mod common;
+
+use burn::backend::NdArray;
+use burn::config::Config;
+use burn::module::Module;
+use burn::record::CompactRecorder;
+use common::TempDir;
+use inside_deep_learning_with_burn::inference::{
+    load_artifact, load_config, load_model, LoadError,
+};
+use inside_deep_learning_with_burn::model::mlp::{Mlp, MlpConfig};
+
+fn config(hidden: usize) -> MlpConfig {
+    MlpConfig::new(2, vec![hidden], 2)
+}
+
+// Saves the config and the weights of an untrained model, as training would
+fn save_artifact(dir: &TempDir, config: &MlpConfig) {
+    config.save(dir.join("config.json")).unwrap();
+    config
+        .init::<NdArray>(&Default::default())
+        .save_file(dir.join("model"), &CompactRecorder::new())
+        .unwrap();
+}
+
+fn load(dir: &TempDir, config: &MlpConfig) -> Result<Mlp<NdArray>, LoadError> {
+    let device = Default::default();
+    load_model(dir.to_str(), config.init::<NdArray>(&device), &device)
+}
+
+#[test]
+fn loads_a_saved_artifact() {
+    let dir = TempDir::new("inference-artifact");
+    save_artifact(&dir, &config(8));
+
+    let (config, _) = load_artifact::<NdArray, MlpConfig, _>(
+        dir.to_str(),
+        |config, device| config.init(device),
+        &Default::default(),
+    )
+    .unwrap();
+
+    assert_eq!(config.hidden, [8]);
+}
+
+#[test]
+fn missing_config_is_reported() {
+    let dir = TempDir::new("inference-no-config");
+
+    let result = load_config::<MlpConfig>(dir.to_str());
+
+    assert!(matches!(
+        result,
+        Err(LoadError::MissingConfig { path }) if path == dir.join("config.json")
+    ));
+}
+
+#[test]
+fn invalid_config_is_reported() {
+    let dir = TempDir::new("inference-bad-config");
+    std::fs::write(dir.join("config.json"), r#"{ "in_features": 2, "hidden": "#).unwrap();
+
+    let result = load_config::<MlpConfig>(dir.to_str());
+
+    assert!(matches!(result, Err(LoadError::InvalidConfig { .. })));
+}
+
+#[test]
+fn missing_record_is_reported() {
+    let dir = TempDir::new("inference-no-record");
+    config(8).save(dir.join("config.json")).unwrap();
+
+    let result = load(&dir, &config(8));
+
+    assert!(matches!(result, Err(LoadError::MissingRecord { .. })));
+}
+
+#[test]
+fn truncated_record_is_reported() {
+    let dir = TempDir::new("inference-truncated");
+    save_artifact(&dir, &config(8));
+    let path = dir.join("model.mpk");
+    let bytes = std::fs::read(&path).unwrap();
+    std::fs::write(&path, &bytes[..bytes.len() / 2]).unwrap();
+
+    let result = load(&dir, &config(8));
+
+    assert!(matches!(result, Err(LoadError::CorruptRecord { .. })));
+}
+
+#[test]
+fn record_of_another_hidden_size_is_reported() {
+    let dir = TempDir::new("inference-shape");
+    save_artifact(&dir, &config(8));
+
+    let result = load(&dir, &config(16));
+
+    assert!(matches!(
+        result,
+        Err(LoadError::ShapeMismatch { expected, found, .. })
+            if expected == [2, 16] && found == [2, 8]
+    ));
+}
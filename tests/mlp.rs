@@ -0,0 +1,224 @@
+mod common;
+
+use burn::backend::{Autodiff, NdArray};
+use burn::data::dataloader::batcher::Batcher;
+use burn::module::{AutodiffModule, Module};
+use burn::record::CompactRecorder;
+use burn::tensor::{Data, Distribution, Tensor};
+use burn::train::{ClassificationOutput, RegressionOutput, TrainOutput, TrainStep, ValidStep};
+use common::TempDir;
+use inside_deep_learning_with_burn::curve_data::batcher::CurveBatcher;
+use inside_deep_learning_with_burn::curve_data::data::{CurveConfig, CurveFunction};
+use inside_deep_learning_with_burn::model::layers::{Activation, Normalization};
+use inside_deep_learning_with_burn::model::mlp::{Mlp, MlpConfig};
+use inside_deep_learning_with_burn::xor_data::batcher::XorBatcher;
+use inside_deep_learning_with_burn::xor_data::data::XorConfig;
+
+type B = NdArray;
+
+fn values<const D: usize>(tensor: Tensor<B, D>) -> Vec<f32> {
+    tensor.into_data().convert::<f32>().value
+}
+
+// The compact recorder saves the weights in half precision
+fn assert_close(actual: &[f32], expected: &[f32]) {
+    assert_eq!(actual.len(), expected.len());
+    for (actual, expected) in actual.iter().zip(expected) {
+        assert!((actual - expected).abs() < 1e-2, "{actual} != {expected}");
+    }
+}
+
+#[test]
+fn activations_are_applied_element_wise() {
+    let device = Default::default();
+    let x = || Tensor::<B, 1>::from_data(Data::from([-2.0, 0.0, 2.0]).convert(), &device);
+
+    let cases = [
+        (Activation::Relu, [0.0, 0.0, 2.0]),
+        (
+            Activation::LeakyRelu {
+                negative_slope: 0.1,
+            },
+            [-0.2, 0.0, 2.0],
+        ),
+        (Activation::Tanh, [-0.9640, 0.0, 0.9640]),
+        (Activation::Sigmoid, [0.1192, 0.5, 0.8808]),
+        (Activation::Gelu, [-0.0455, 0.0, 1.9545]),
+    ];
+
+    for (activation, expected) in cases {
+        let actual = values(activation.forward(x()));
+        assert!(
+            actual
+                .iter()
+                .zip(expected)
+                .all(|(actual, expected)| (actual - expected).abs() < 1e-3),
+            "{activation:?} gave {actual:?}"
+        );
+    }
+}
+
+#[test]
+fn forward_gives_out_features_for_every_config() {
+    let device = Default::default();
+    let x = Tensor::<B, 2>::random([5, 3], Distribution::Default, &device);
+
+    for hidden in [vec![], vec![8], vec![16, 8, 4]] {
+        for normalization in [None, Some(Normalization::Batch), Some(Normalization::Layer)] {
+            let model = MlpConfig::new(3, hidden.clone(), 2)
+                .with_normalization(normalization)
+                .with_dropout(Some(0.5))
+                .init::<B>(&device);
+
+            assert_eq!(
+                model.forward(x.clone()).dims(),
+                [5, 2],
+                "hidden={hidden:?} normalization={normalization:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn normalization_adds_parameters_per_hidden_feature() {
+    let device = Default::default();
+    let config = MlpConfig::new(2, vec![8, 4], 3);
+
+    // 2x8 + 8, 8x4 + 4 and 4x3 + 3
+    let linear = 24 + 36 + 15;
+    assert_eq!(config.init::<B>(&device).num_params(), linear);
+
+    // Batch normalization also records the running mean and variance of each feature
+    for (normalization, per_feature) in [(Normalization::Batch, 4), (Normalization::Layer, 2)] {
+        let model = config
+            .clone()
+            .with_normalization(Some(normalization))
+            .init::<B>(&device);
+
+        assert_eq!(
+            model.num_params(),
+            linear + per_feature * (8 + 4),
+            "{normalization:?}"
+        );
+    }
+}
+
+#[test]
+fn layer_normalization_ignores_the_scale_of_the_inputs() {
+    let device = Default::default();
+    let config = MlpConfig::new(2, vec![16], 1).with_activation(Activation::Relu);
+    let x = Tensor::<B, 2>::random([4, 2], Distribution::Normal(0.0, 1.0), &device);
+    // Large enough for the biases of the hidden layer to be negligible
+    let difference = |model: &Mlp<B>| {
+        let small = model.forward(x.clone() * 1000.0);
+        let large = model.forward(x.clone() * 2000.0);
+        (small - large).abs().max().into_scalar()
+    };
+
+    let normalized = config
+        .clone()
+        .with_normalization(Some(Normalization::Layer))
+        .init::<B>(&device);
+    assert!(difference(&normalized) < 1e-2);
+
+    let unnormalized = config.init::<B>(&device);
+    assert!(difference(&unnormalized) > 1.0);
+}
+
+#[test]
+fn output_activation_is_applied_to_the_head() {
+    let device = Default::default();
+    let model = MlpConfig::new(2, vec![8], 4)
+        .with_output(Some(Activation::Sigmoid))
+        .init::<B>(&device);
+    let x = Tensor::<B, 2>::random([16, 2], Distribution::Normal(0.0, 10.0), &device);
+
+    let output = values(model.forward(x));
+    assert!(output.iter().all(|y| (0.0..=1.0).contains(y)), "{output:?}");
+}
+
+#[test]
+fn dropout_is_only_active_when_training() {
+    let device = Default::default();
+    let config = MlpConfig::new(2, vec![64], 2).with_dropout(Some(0.5));
+    let x = Tensor::<B, 2>::random([8, 2], Distribution::Default, &device);
+
+    let model = config.init::<B>(&device);
+    assert_eq!(
+        values(model.forward(x.clone())),
+        values(model.forward(x.clone()))
+    );
+
+    let model = config.init::<Autodiff<B>>(&device);
+    let x = Tensor::<Autodiff<B>, 2>::from_inner(x);
+    let first = model.forward(x.clone()).inner();
+    let second = model.forward(x.clone()).inner();
+    assert_ne!(values(first), values(second));
+
+    // The validation model drops the autodiff backend and with it the dropout
+    let valid = model.valid();
+    let x = x.inner();
+    assert_eq!(
+        values(valid.forward(x.clone())),
+        values(valid.forward(x.clone()))
+    );
+}
+
+#[test]
+fn reloaded_model_gives_the_same_outputs() {
+    let dir = TempDir::new("mlp-record");
+    let device = Default::default();
+    let config = MlpConfig::new(2, vec![8, 8], 3)
+        .with_activation(Activation::Gelu)
+        .with_normalization(Some(Normalization::Layer))
+        .with_dropout(Some(0.1));
+    let x = Tensor::<B, 2>::random([6, 2], Distribution::Default, &device);
+
+    let model = config.init::<B>(&device);
+    model
+        .clone()
+        .save_file(dir.join("model"), &CompactRecorder::new())
+        .unwrap();
+
+    let reloaded = config
+        .init::<B>(&device)
+        .load_file(dir.join("model"), &CompactRecorder::new(), &device)
+        .unwrap();
+
+    assert_close(
+        &values(reloaded.forward(x.clone())),
+        &values(model.forward(x)),
+    );
+}
+
+#[test]
+fn steps_run_on_the_batches_of_every_dataset() {
+    let device = Default::default();
+
+    let classifier = MlpConfig::new(2, vec![8], 2).init::<Autodiff<B>>(&device);
+    let items = XorConfig::new(10).generate();
+    let output: ClassificationOutput<B> = ValidStep::step(
+        &classifier.valid(),
+        XorBatcher::<B>::new(device).batch(items.clone()),
+    );
+    assert_eq!(output.output.dims(), [10, 2]);
+    let _: TrainOutput<ClassificationOutput<_>> = TrainStep::step(
+        &classifier,
+        XorBatcher::<Autodiff<B>>::new(device).batch(items),
+    );
+
+    let regressor = MlpConfig::new(1, vec![8], 1).init::<Autodiff<B>>(&device);
+    let line = CurveFunction::Polynomial {
+        coefficients: vec![0.0, 1.0],
+    };
+    let items = CurveConfig::new(line, -1.0, 1.0, 10).generate();
+    let output: RegressionOutput<B> = ValidStep::step(
+        &regressor.valid(),
+        CurveBatcher::<B>::new(device).batch(items.clone()),
+    );
+    assert_eq!(output.output.dims(), [10, 1]);
+    let _: TrainOutput<RegressionOutput<_>> = TrainStep::step(
+        &regressor,
+        CurveBatcher::<Autodiff<B>>::new(device).batch(items),
+    );
+}
@@ -0,0 +1,122 @@
+mod common;
+
+use burn::backend::NdArray;
+use burn::config::Config;
+use burn::module::Module;
+use burn::optim::AdamConfig;
+use burn::record::CompactRecorder;
+use common::TempDir;
+use inside_deep_learning_with_burn::mlp_experiment::{self, MlpDataConfig, MlpExperimentConfig};
+use inside_deep_learning_with_burn::model::mlp::MlpConfig;
+use inside_deep_learning_with_burn::moons_data::data::{MoonDatasetConfig, MoonsConfig};
+use inside_deep_learning_with_burn::optimizer::OptimizerConfig;
+use inside_deep_learning_with_burn::overrides;
+use inside_deep_learning_with_burn::split::SplitConfig;
+use inside_deep_learning_with_burn::toy_data::data::ToyDatasetConfig;
+use inside_deep_learning_with_burn::train::TrainingConfig;
+
+fn toy(train: f32) -> MlpExperimentConfig {
+    MlpExperimentConfig::new(
+        MlpConfig::new(1, vec![4], 1),
+        TrainingConfig::new(OptimizerConfig::Adam(AdamConfig::new())).with_num_workers(1),
+        MlpDataConfig::Toy(ToyDatasetConfig::new(
+            0.0,
+            20.0,
+            50,
+            SplitConfig::new(train, 0.0, 12, false),
+        )),
+    )
+}
+
+fn moons() -> MlpExperimentConfig {
+    MlpExperimentConfig::new(
+        MlpConfig::new(2, vec![4], 2),
+        TrainingConfig::new(OptimizerConfig::Adam(AdamConfig::new())).with_num_workers(1),
+        MlpDataConfig::Moons(MoonDatasetConfig::new(
+            MoonsConfig::new(20, 20),
+            SplitConfig::new(0.5, 0.25, 12, true),
+        )),
+    )
+}
+
+// Saves the config and an untrained model the way a training leaves them
+fn write_artifact(name: &str, config: &MlpExperimentConfig) -> TempDir {
+    let dir = TempDir::new(name);
+    config.save(dir.join("config.json")).unwrap();
+    config
+        .model
+        .init::<NdArray>(&Default::default())
+        .save_file(dir.join("model"), &CompactRecorder::new())
+        .unwrap();
+
+    dir
+}
+
+#[test]
+fn config_round_trips_with_its_data() {
+    let dir = TempDir::new("mlp-experiment-config");
+
+    for config in [toy(0.8), moons()] {
+        config.save(dir.join("config.json")).unwrap();
+        let loaded = MlpExperimentConfig::load(dir.join("config.json")).unwrap();
+
+        assert_eq!(format!("{:?}", loaded.data), format!("{:?}", config.data));
+    }
+}
+
+#[test]
+fn toy_data_is_evaluated_as_a_regression() {
+    let dir = write_artifact("mlp-experiment-toy", &toy(0.8));
+
+    let evaluation = mlp_experiment::evaluate::<NdArray>(dir.to_str(), Default::default()).unwrap();
+
+    assert_eq!(evaluation.num_items, 10);
+    assert!(evaluation.loss.is_some_and(f64::is_finite));
+    assert_eq!(evaluation.accuracy, None);
+}
+
+#[test]
+fn moons_are_evaluated_as_a_classification() {
+    let dir = write_artifact("mlp-experiment-moons", &moons());
+
+    let evaluation = mlp_experiment::evaluate::<NdArray>(dir.to_str(), Default::default()).unwrap();
+
+    assert_eq!(evaluation.num_items, 10);
+    assert!(evaluation
+        .accuracy
+        .is_some_and(|accuracy| (0.0..=100.0).contains(&accuracy)));
+}
+
+#[test]
+fn empty_test_split_has_no_metrics() {
+    let dir = write_artifact("mlp-experiment-empty", &toy(1.0));
+
+    let evaluation = mlp_experiment::evaluate::<NdArray>(dir.to_str(), Default::default()).unwrap();
+
+    assert_eq!(evaluation.num_items, 0);
+    assert_eq!(evaluation.loss, None);
+    assert_eq!(evaluation.to_string(), "No items to evaluate");
+}
+
+#[test]
+fn inference_plots_the_predictions() {
+    let dir = write_artifact("mlp-experiment-toy-plot", &toy(0.8));
+    mlp_experiment::infer::<NdArray>(dir.to_str(), Default::default());
+    assert!(dir.join("model.html").exists());
+
+    let dir = write_artifact("mlp-experiment-moons-plot", &moons());
+    mlp_experiment::infer::<NdArray>(dir.to_str(), Default::default());
+    assert!(dir.join("model.html").exists());
+    assert!(dir.join("decision_boundary.html").exists());
+}
+
+#[test]
+fn data_can_be_overridden() {
+    let overrides = [("data.Moons.moons.noise".to_string(), "0.1".to_string())];
+    let config = overrides::apply(&moons(), &overrides).unwrap();
+
+    match config.data {
+        MlpDataConfig::Moons(data) => assert_eq!(data.moons.noise, Some(0.1)),
+        MlpDataConfig::Toy(_) => panic!("Overriding a field should keep the variant"),
+    }
+}
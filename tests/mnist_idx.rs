@@ -0,0 +1,255 @@
+mod common;
+
+use std::path::{Path, PathBuf};
+
+use burn::data::dataset::Dataset;
+use common::TempDir;
+use inside_deep_learning_with_burn::image_experiment::ImageDataConfig;
+use inside_deep_learning_with_burn::mist_data::idx::{IdxError, MnistIdxDataset};
+use inside_deep_learning_with_burn::mist_data::variants::{EmnistSplit, MnistVariant};
+use inside_deep_learning_with_burn::split::Split;
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/mnist");
+
+// Pixel values used when generating the fixture images
+fn pixel(image: usize, i: usize) -> f32 {
+    ((image * 37 + i) % 256) as f32
+}
+
+fn write_labels(dir: &Path, magic: u32, labels: &[u8]) -> PathBuf {
+    let path = dir.join("labels");
+    let mut bytes = magic.to_be_bytes().to_vec();
+    bytes.extend((labels.len() as u32).to_be_bytes());
+    bytes.extend(labels);
+    std::fs::write(&path, bytes).unwrap();
+    path
+}
+
+// Writes `{name}-images-idx3-ubyte` and `{name}-labels-idx1-ubyte` with blank images
+fn write_split(dir: &Path, name: &str, labels: &[u8]) {
+    let count = (labels.len() as u32).to_be_bytes();
+
+    let mut images = 0x803u32.to_be_bytes().to_vec();
+    images.extend(count);
+    images.extend(28u32.to_be_bytes());
+    images.extend(28u32.to_be_bytes());
+    images.extend(vec![0; labels.len() * 28 * 28]);
+    std::fs::write(dir.join(format!("{name}-images-idx3-ubyte")), images).unwrap();
+
+    let mut bytes = 0x801u32.to_be_bytes().to_vec();
+    bytes.extend(count);
+    bytes.extend(labels);
+    std::fs::write(dir.join(format!("{name}-labels-idx1-ubyte")), bytes).unwrap();
+}
+
+#[test]
+fn reads_gzip_train_split() {
+    let dataset = MnistIdxDataset::train(FIXTURES).unwrap();
+
+    assert_eq!(dataset.len(), 3);
+    let labels: Vec<u8> = dataset.iter().map(|item| item.label).collect();
+    assert_eq!(labels, vec![5, 0, 4]);
+
+    let item = dataset.get(2).unwrap();
+    assert_eq!(item.image[0][0], pixel(2, 0));
+    assert_eq!(item.image[1][3], pixel(2, 28 + 3));
+    assert_eq!(item.image[27][27], pixel(2, 28 * 28 - 1));
+}
+
+#[test]
+fn reads_raw_test_split() {
+    let dataset = MnistIdxDataset::test(FIXTURES).unwrap();
+
+    assert_eq!(dataset.len(), 2);
+    assert_eq!(dataset.get(1).unwrap().label, 2);
+    assert_eq!(dataset.get(1).unwrap().image[10][5], pixel(1, 10 * 28 + 5));
+}
+
+#[test]
+fn missing_directory_is_reported() {
+    let result = MnistIdxDataset::train("does/not/exist");
+
+    assert!(matches!(result, Err(IdxError::MissingFile { .. })));
+}
+
+#[test]
+fn bad_magic_number_is_reported() {
+    let dir = TempDir::new("mnist-idx-magic");
+    let labels = write_labels(dir.path(), 0x0000_0803, &[1, 2, 3]);
+    let images = Path::new(FIXTURES).join("train-images-idx3-ubyte.gz");
+
+    let result = MnistIdxDataset::from_files(&images, &labels);
+
+    assert!(matches!(
+        result,
+        Err(IdxError::BadMagic {
+            expected: 0x0000_0801,
+            found: 0x0000_0803,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn mismatched_counts_are_reported() {
+    let dir = TempDir::new("mnist-idx-count");
+    let labels = write_labels(dir.path(), 0x0000_0801, &[1, 2]);
+    let images = Path::new(FIXTURES).join("train-images-idx3-ubyte.gz");
+
+    let result = MnistIdxDataset::from_files(&images, &labels);
+
+    assert!(matches!(
+        result,
+        Err(IdxError::CountMismatch {
+            images: 3,
+            labels: 2
+        })
+    ));
+}
+
+#[test]
+fn truncated_file_is_reported() {
+    let dir = TempDir::new("mnist-idx-truncated");
+    let path = dir.join("labels");
+    std::fs::write(&path, [0, 0, 8, 1, 0, 0, 0, 5, 1, 2]).unwrap();
+    let images = Path::new(FIXTURES).join("train-images-idx3-ubyte.gz");
+
+    let result = MnistIdxDataset::from_files(&images, &path);
+
+    assert!(matches!(
+        result,
+        Err(IdxError::Truncated {
+            expected: 13,
+            found: 10,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn trailing_data_is_reported() {
+    let dir = TempDir::new("mnist-idx-trailing");
+    let path = dir.join("labels");
+    std::fs::write(&path, [0, 0, 8, 1, 0, 0, 0, 2, 1, 2, 3, 4]).unwrap();
+    let images = Path::new(FIXTURES).join("train-images-idx3-ubyte.gz");
+
+    let result = MnistIdxDataset::from_files(&images, &path);
+
+    assert!(matches!(
+        result,
+        Err(IdxError::TrailingData {
+            expected: 10,
+            found: 12,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn variants_without_download_need_a_directory() {
+    for variant in [
+        MnistVariant::FashionMnist,
+        MnistVariant::Kmnist,
+        MnistVariant::Emnist {
+            split: EmnistSplit::Letters,
+        },
+    ] {
+        assert!(matches!(
+            variant.dataset(None, Split::Train),
+            Err(IdxError::NoDirectory { .. })
+        ));
+    }
+}
+
+#[test]
+fn number_of_classes_follows_the_dataset() {
+    let classes = |dataset: MnistVariant| {
+        ImageDataConfig::new()
+            .with_dataset(dataset.into())
+            .num_classes()
+    };
+    let emnist = |split| MnistVariant::Emnist { split };
+
+    assert_eq!(classes(MnistVariant::Mnist), 10);
+    assert_eq!(classes(MnistVariant::FashionMnist), 10);
+    assert_eq!(classes(emnist(EmnistSplit::Balanced)), 47);
+    assert_eq!(classes(emnist(EmnistSplit::ByClass)), 62);
+    assert_eq!(classes(emnist(EmnistSplit::ByMerge)), 47);
+    assert_eq!(classes(emnist(EmnistSplit::Letters)), 26);
+    assert_eq!(classes(emnist(EmnistSplit::Digits)), 10);
+}
+
+#[test]
+fn emnist_letters_are_labelled_from_zero() {
+    let dir = TempDir::new("mnist-idx-letters");
+    write_split(dir.path(), "emnist-letters-train", &[1, 26, 3]);
+    let letters = MnistVariant::Emnist {
+        split: EmnistSplit::Letters,
+    };
+
+    let dataset = letters.dataset(Some(dir.to_str()), Split::Train).unwrap();
+
+    let labels: Vec<u8> = dataset.iter().map(|item| item.label).collect();
+    assert_eq!(labels, [0, 25, 2]);
+}
+
+#[test]
+fn labels_outside_of_the_classes_are_reported() {
+    let dir = TempDir::new("mnist-idx-labels");
+    write_split(dir.path(), "emnist-letters-train", &[1, 0]);
+    write_split(dir.path(), "emnist-letters-test", &[27]);
+    write_split(dir.path(), "train", &[9, 3, 10]);
+    let letters = MnistVariant::Emnist {
+        split: EmnistSplit::Letters,
+    };
+
+    assert!(matches!(
+        letters.dataset(Some(dir.to_str()), Split::Train),
+        Err(IdxError::BadLabel {
+            index: 1,
+            label: 0,
+            min: 1,
+            max: 26,
+        })
+    ));
+    assert!(matches!(
+        letters.dataset(Some(dir.to_str()), Split::Test),
+        Err(IdxError::BadLabel {
+            index: 0,
+            label: 27,
+            ..
+        })
+    ));
+    assert!(matches!(
+        MnistVariant::Mnist.dataset(Some(dir.to_str()), Split::Train),
+        Err(IdxError::BadLabel {
+            index: 2,
+            label: 10,
+            min: 0,
+            max: 9,
+        })
+    ));
+}
+
+#[test]
+fn variants_have_no_validation_split() {
+    for dir in [None, Some(FIXTURES)] {
+        assert!(matches!(
+            MnistVariant::Mnist.dataset(dir, Split::Valid),
+            Err(IdxError::NoSplit {
+                split: Split::Valid,
+                ..
+            })
+        ));
+    }
+}
+
+#[test]
+fn variants_read_the_test_split() {
+    let dataset = MnistVariant::Mnist
+        .dataset(Some(FIXTURES), Split::Test)
+        .unwrap();
+
+    assert_eq!(dataset.len(), 2);
+    assert_eq!(dataset.get(1).unwrap().label, 2);
+}
@@ -0,0 +1,98 @@
+use inside_deep_learning_with_burn::moons_data::data::MoonsConfig;
+
+// sklearn.datasets.make_moons(n_samples=(4, 3), shuffle=False, noise=None)
+const SKLEARN_MOONS: [[f64; 2]; 7] = [
+    [1.0, 0.0],
+    [0.5000000000000001, 0.8660254037844386],
+    [-0.4999999999999998, 0.8660254037844387],
+    [-1.0, 1.2246467991473532e-16],
+    [0.0, 0.5],
+    [0.9999999999999999, -0.5],
+    [2.0, 0.4999999999999999],
+];
+const SKLEARN_LABELS: [i8; 7] = [0, 0, 0, 0, 1, 1, 1];
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 1e-12,
+        "expected {expected}, found {actual}"
+    );
+}
+
+#[test]
+fn matches_sklearn_without_noise() {
+    let (x, y) = MoonsConfig::new(4, 3).with_shuffle(false).arrays();
+
+    assert_eq!(x.shape(), &[7, 2]);
+    assert_eq!(y.to_vec(), SKLEARN_LABELS.to_vec());
+    for (row, expected) in x.rows().into_iter().zip(SKLEARN_MOONS) {
+        assert_close(row[0], expected[0]);
+        assert_close(row[1], expected[1]);
+    }
+}
+
+#[test]
+fn splits_samples_like_sklearn() {
+    let config = MoonsConfig::from_samples(7);
+
+    assert_eq!(config.n_samples_out, 3);
+    assert_eq!(config.n_samples_in, 4);
+}
+
+#[test]
+fn applies_offset_and_scale() {
+    let (x, _) = MoonsConfig::new(4, 3)
+        .with_shuffle(false)
+        .with_scale(2.0)
+        .with_offset([1.0, -1.0])
+        .arrays();
+
+    for (row, expected) in x.rows().into_iter().zip(SKLEARN_MOONS) {
+        assert_close(row[0], 2.0 * expected[0] + 1.0);
+        assert_close(row[1], 2.0 * expected[1] - 1.0);
+    }
+}
+
+#[test]
+fn shuffle_is_a_seeded_permutation() {
+    let config = MoonsConfig::new(4, 3).with_seed(7);
+    let (x, y) = config.arrays();
+    let (x_again, y_again) = config.arrays();
+
+    assert_eq!(x, x_again);
+    assert_eq!(y, y_again);
+
+    let mut points: Vec<(u64, u64, i8)> = x
+        .rows()
+        .into_iter()
+        .zip(y.iter())
+        .map(|(row, y)| (row[0].to_bits(), row[1].to_bits(), *y))
+        .collect();
+    let mut expected: Vec<(u64, u64, i8)> = SKLEARN_MOONS
+        .iter()
+        .zip(SKLEARN_LABELS)
+        .map(|(row, y)| (row[0].to_bits(), row[1].to_bits(), y))
+        .collect();
+    points.sort();
+    expected.sort();
+
+    assert_eq!(points, expected);
+}
+
+#[test]
+fn noise_is_reproducible_and_scaled() {
+    let config = MoonsConfig::new(2000, 2000)
+        .with_shuffle(false)
+        .with_noise(Some(0.1));
+    let (noisy, _) = config.arrays();
+    let (again, _) = config.arrays();
+    let (other, _) = config.clone().with_seed(22).arrays();
+    let (clean, _) = config.with_noise(None).arrays();
+
+    assert_eq!(noisy, again);
+    assert_ne!(noisy, other);
+
+    let residuals = noisy - clean;
+    let std = residuals.std(0.0);
+    assert!((std - 0.1).abs() < 0.005, "noise std was {std}");
+}
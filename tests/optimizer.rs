@@ -0,0 +1,95 @@
+use burn::backend::{Autodiff, NdArray};
+use burn::nn::{Linear, LinearConfig};
+use burn::optim::momentum::MomentumConfig;
+use burn::optim::{AdamConfig, AdamWConfig, GradientsParams, Optimizer, RmsPropConfig, SgdConfig};
+use burn::tensor::{Data, Tensor};
+use inside_deep_learning_with_burn::optimizer::{OptimizerConfig, OptimizerError};
+
+type B = Autodiff<NdArray>;
+
+fn optimizers() -> Vec<OptimizerConfig> {
+    vec![
+        OptimizerConfig::Sgd(SgdConfig::new().with_momentum(Some(MomentumConfig::new()))),
+        OptimizerConfig::Adam(AdamConfig::new()),
+        OptimizerConfig::AdamW(AdamWConfig::new()),
+        OptimizerConfig::RmsProp(RmsPropConfig::new().with_momentum(0.9)),
+    ]
+}
+
+fn model() -> Linear<B> {
+    LinearConfig::new(2, 3).init(&Default::default())
+}
+
+// One step on the mean squared output of a fixed batch
+fn step<O: Optimizer<Linear<B>, B>>(optimizer: &mut O, model: Linear<B>) -> Linear<B> {
+    let x = Tensor::<B, 2>::from_floats([[1.0, 2.0], [3.0, -1.0]], &Default::default());
+    let loss = model.forward(x).powf_scalar(2.0).mean();
+    let grads = GradientsParams::from_grads(loss.backward(), &model);
+    optimizer.step(0.1, model, grads)
+}
+
+fn weights(model: &Linear<B>) -> Data<f32, 2> {
+    model.weight.val().into_data().convert()
+}
+
+#[test]
+fn every_optimizer_updates_the_model() {
+    for config in optimizers() {
+        let mut optimizer = config.init::<B, Linear<B>>();
+        let initial = model();
+        let before = weights(&initial);
+
+        let trained = step(&mut optimizer, initial);
+
+        assert_ne!(weights(&trained), before, "{}", config.name());
+    }
+}
+
+#[test]
+fn records_resume_the_same_steps() {
+    for config in optimizers() {
+        let mut optimizer = config.init::<B, Linear<B>>();
+        let model = step(&mut optimizer, model());
+        let model = step(&mut optimizer, model);
+
+        let record = optimizer.to_record();
+        let saved = [
+            record.0.is_some(),
+            record.1.is_some(),
+            record.2.is_some(),
+            record.3.is_some(),
+        ];
+        assert_eq!(saved.iter().filter(|saved| **saved).count(), 1);
+        let mut resumed = config.init::<B, Linear<B>>().load_record(record);
+
+        let expected = step(&mut optimizer, model.clone());
+        let found = step(&mut resumed, model.clone());
+        assert_eq!(weights(&found), weights(&expected), "{}", config.name());
+
+        // Without its state, e.g. the momentum, the optimizer takes another step
+        let mut fresh = config.init::<B, Linear<B>>();
+        assert_ne!(
+            weights(&step(&mut fresh, model.clone())),
+            weights(&expected),
+            "{}",
+            config.name()
+        );
+    }
+}
+
+#[test]
+fn resuming_needs_the_same_optimizer() {
+    let adam = OptimizerConfig::Adam(AdamConfig::new());
+    let sgd = OptimizerConfig::Sgd(SgdConfig::new());
+
+    // Only the kind of optimizer matters, not its options
+    let other_adam = OptimizerConfig::Adam(AdamConfig::new().with_epsilon(1e-6));
+    assert!(adam.check_resume(&other_adam).is_ok());
+    assert!(matches!(
+        adam.check_resume(&sgd),
+        Err(OptimizerError::Mismatch {
+            saved: "Sgd",
+            configured: "Adam",
+        })
+    ));
+}
@@ -0,0 +1,84 @@
+use burn::backend::NdArray;
+use burn::tensor::{Distribution, Tensor};
+use inside_deep_learning_with_burn::model::cnn::{ConvBlockConfig, Readout};
+use inside_deep_learning_with_burn::overrides;
+
+#[path = "../examples/3-5-pooling/model.rs"]
+mod model;
+
+use model::ModelConfig;
+
+// (filters, channels, height, width, stages, convs_per_stage)
+const CONFIGS: [(usize, usize, usize, usize, usize, usize); 7] = [
+    (16, 1, 28, 28, 2, 3),
+    (8, 1, 28, 28, 2, 3),
+    (24, 1, 28, 28, 2, 1),
+    (16, 3, 32, 32, 3, 2),
+    (4, 1, 27, 31, 2, 2),
+    (6, 3, 13, 9, 1, 1),
+    (5, 1, 29, 29, 3, 0),
+];
+
+#[test]
+fn forward_gives_one_logit_per_class_for_every_config() {
+    let device = Default::default();
+
+    for (filters, channels, height, width, stages, convs_per_stage) in CONFIGS {
+        let model = ModelConfig::new(filters, height, width)
+            .with_channels(channels)
+            .with_stages(stages)
+            .with_convs_per_stage(convs_per_stage)
+            .cnn(10)
+            .init::<NdArray>(&device);
+        let images = Tensor::<NdArray, 4>::random(
+            [3, channels, height, width],
+            Distribution::Default,
+            &device,
+        );
+
+        assert_eq!(
+            model.forward(images).dims(),
+            [3, 10],
+            "filters={filters} channels={channels} size={height}x{width} stages={stages}"
+        );
+    }
+}
+
+#[test]
+#[should_panic(expected = "shrink")]
+fn too_many_stages_for_the_image_size_panics() {
+    ModelConfig::new(16, 7, 7)
+        .with_stages(3)
+        .cnn(10)
+        .init::<NdArray>(&Default::default());
+}
+
+#[test]
+fn stages_are_expanded_from_the_overridden_keys() {
+    let overrides = [("filters", "32"), ("stages", "3"), ("convs_per_stage", "1")]
+        .map(|(key, value)| (key.to_string(), value.to_string()));
+    let config = overrides::apply(&ModelConfig::new(16, 28, 28), &overrides)
+        .unwrap()
+        .cnn(10);
+
+    let channels: Vec<usize> = config.blocks.iter().map(|block| block.channels).collect();
+    assert_eq!(channels, [32, 64, 128]);
+}
+
+#[test]
+fn custom_blocks_replace_the_stages() {
+    let device = Default::default();
+    let config = ModelConfig::new(16, 28, 28)
+        .with_blocks(Some(vec![ConvBlockConfig::new(4), ConvBlockConfig::new(8)]))
+        .with_readout(Readout::GlobalAveragePooling)
+        .cnn(10);
+
+    assert_eq!(config.blocks.len(), 2);
+    assert_eq!(config.readout, Readout::GlobalAveragePooling);
+
+    let images = Tensor::<NdArray, 4>::random([2, 1, 28, 28], Distribution::Default, &device);
+    assert_eq!(
+        config.init::<NdArray>(&device).forward(images).dims(),
+        [2, 10]
+    );
+}
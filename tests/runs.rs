@@ -0,0 +1,147 @@
+mod common;
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use burn::config::Config;
+use burn::optim::AdamConfig;
+use common::TempDir;
+use inside_deep_learning_with_burn::optimizer::OptimizerConfig;
+use inside_deep_learning_with_burn::runs::{
+    data_files, dataset_fingerprint, new_run_dir, Run, RunManifest, RunReport,
+};
+use inside_deep_learning_with_burn::train::{ArtifactConfig, TrainingConfig};
+
+#[derive(Config)]
+struct ExperimentConfig {
+    training: TrainingConfig,
+}
+
+impl ArtifactConfig for ExperimentConfig {
+    fn training(&self) -> &TrainingConfig {
+        &self.training
+    }
+}
+
+fn config(seed: u64) -> ExperimentConfig {
+    ExperimentConfig::new(
+        TrainingConfig::new(OptimizerConfig::Adam(AdamConfig::new())).with_seed(seed),
+    )
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("runs-{name}-{}", std::process::id()));
+    std::fs::remove_dir_all(&dir).ok();
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn write_run(dir: &Path, loss: f64) {
+    std::fs::create_dir_all(dir).unwrap();
+    std::fs::write(dir.join("config.json"), "{}").unwrap();
+
+    let id = dir.file_name().unwrap().to_string_lossy().into_owned();
+    RunManifest::new(
+        id,
+        "2024-05-17T09:30:00Z".to_string(),
+        42,
+        "ndarray".to_string(),
+        "0123456789abcdef".to_string(),
+        BTreeMap::from([("valid/Loss".to_string(), loss)]),
+        1.0,
+    )
+    .save_to(dir.to_str().unwrap())
+    .unwrap();
+}
+
+#[test]
+fn report_includes_the_sweep_runs() {
+    let runs_dir = temp_dir("report");
+    write_run(&runs_dir.join("20240517-093000-00000001"), 0.5);
+    write_run(
+        &runs_dir.join("sweep-20240518-100000/20240518-100001-00000002"),
+        0.4,
+    );
+    write_run(&runs_dir.join("20240519-080000-00000003"), 0.3);
+    // Neither a run nor a sweep
+    std::fs::create_dir_all(runs_dir.join("plots")).unwrap();
+
+    let report = RunReport::load(runs_dir.to_str().unwrap()).unwrap();
+    std::fs::remove_dir_all(&runs_dir).unwrap();
+
+    let ids: Vec<&str> = report
+        .runs
+        .iter()
+        .map(|(manifest, _)| manifest.id.as_str())
+        .collect();
+    assert_eq!(
+        ids,
+        [
+            "20240517-093000-00000001",
+            "sweep-20240518-100000/20240518-100001-00000002",
+            "20240519-080000-00000003",
+        ]
+    );
+    assert!(report
+        .to_csv()
+        .contains("sweep-20240518-100000/20240518-100001-00000002"));
+}
+
+#[test]
+fn fingerprint_changes_with_the_data_files() {
+    let dir = temp_dir("fingerprint");
+    std::fs::create_dir_all(dir.join("train")).unwrap();
+    std::fs::write(dir.join("train/images"), [1, 2, 3]).unwrap();
+    std::fs::write(dir.join("labels"), [1]).unwrap();
+
+    let files = data_files(&dir);
+    assert_eq!(files, [dir.join("labels"), dir.join("train/images")]);
+
+    let fingerprint = dataset_fingerprint("Mnist", [3, 1], &files);
+    assert_eq!(fingerprint, dataset_fingerprint("Mnist", [3, 1], &files));
+    assert_ne!(fingerprint, dataset_fingerprint("Mnist", [2, 1], &files));
+    assert_ne!(
+        fingerprint,
+        dataset_fingerprint("FashionMnist", [3, 1], &files)
+    );
+
+    std::fs::write(dir.join("train/images"), [1, 2, 3, 4]).unwrap();
+    let changed = dataset_fingerprint("Mnist", [3, 1], &data_files(&dir));
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_ne!(fingerprint, changed);
+}
+
+#[test]
+fn runs_of_the_same_config_get_their_own_directory() {
+    let runs_dir = TempDir::new("runs-same-config");
+
+    let dirs: Vec<String> = (0..3)
+        .map(|_| new_run_dir(runs_dir.to_str(), &config(42)).unwrap())
+        .collect();
+
+    assert_ne!(dirs[0], dirs[1]);
+    assert_ne!(dirs[1], dirs[2]);
+    assert_ne!(dirs[0], dirs[2]);
+    for dir in &dirs {
+        assert!(Path::new(dir).is_dir(), "{dir}");
+    }
+}
+
+#[test]
+fn resumed_runs_record_the_resumed_seed_and_backend() {
+    let dir = TempDir::new("runs-resumed");
+    Run::start(dir.to_str(), &config(42), "ndarray".to_string(), [10, 2]);
+    let mut manifest = RunManifest::load_from(dir.to_str()).unwrap();
+    manifest.duration_secs = 5.0;
+    manifest.save_to(dir.to_str()).unwrap();
+
+    Run::start(dir.to_str(), &config(7), "wgpu".to_string(), [10, 2]);
+    let resumed = RunManifest::load_from(dir.to_str()).unwrap();
+
+    assert_eq!(resumed.seed, 7);
+    assert_eq!(resumed.backend, "wgpu");
+    assert_eq!(resumed.id, manifest.id);
+    assert_eq!(resumed.started_at, manifest.started_at);
+    assert_eq!(resumed.duration_secs, 5.0);
+}
@@ -0,0 +1,177 @@
+use burn::backend::NdArray;
+use burn::lr_scheduler::LrScheduler;
+use inside_deep_learning_with_burn::scheduler::{Scheduler, SchedulerConfig};
+
+// Learning rate of 1 over 10 epochs of 10 iterations unless stated otherwise
+fn scheduler(config: SchedulerConfig) -> Scheduler {
+    config.init(1.0, 10, 10)
+}
+
+fn assert_lr(scheduler: &Scheduler, iteration: usize, expected: f64) {
+    let found = scheduler.learning_rate_at(iteration);
+    assert!(
+        (found - expected).abs() < 1e-9,
+        "iteration {iteration}: expected {expected}, found {found}"
+    );
+}
+
+#[test]
+fn constant_keeps_the_learning_rate() {
+    let scheduler = SchedulerConfig::Constant.init(0.01, 10, 10);
+
+    for iteration in [0, 1, 50, 99] {
+        assert_lr(&scheduler, iteration, 0.01);
+    }
+}
+
+#[test]
+fn step_decay_multiplies_every_step_size_epochs() {
+    let scheduler = scheduler(SchedulerConfig::StepDecay {
+        step_size: 2,
+        gamma: 0.5,
+    });
+
+    assert_lr(&scheduler, 0, 1.0);
+    assert_lr(&scheduler, 19, 1.0);
+    assert_lr(&scheduler, 20, 0.5);
+    assert_lr(&scheduler, 39, 0.5);
+    assert_lr(&scheduler, 40, 0.25);
+    assert_lr(&scheduler, 99, 0.0625);
+}
+
+#[test]
+fn exponential_multiplies_every_epoch() {
+    let scheduler = scheduler(SchedulerConfig::Exponential { gamma: 0.9 });
+
+    assert_lr(&scheduler, 0, 1.0);
+    assert_lr(&scheduler, 9, 1.0);
+    assert_lr(&scheduler, 10, 0.9);
+    assert_lr(&scheduler, 25, 0.81);
+}
+
+#[test]
+fn cosine_warm_restarts_with_longer_periods() {
+    let scheduler = scheduler(SchedulerConfig::CosineWarmRestarts {
+        period: 2,
+        period_mult: 2,
+        min_lr: 0.1,
+    });
+
+    // First period of 2 epochs, halfway at epoch 1
+    assert_lr(&scheduler, 0, 1.0);
+    assert_lr(&scheduler, 10, 0.55);
+    assert!(scheduler.learning_rate_at(19) < 0.11);
+    // Second period of 4 epochs
+    assert_lr(&scheduler, 20, 1.0);
+    assert_lr(&scheduler, 40, 0.55);
+    assert!(scheduler.learning_rate_at(59) < 0.11);
+    // Third period of 8 epochs
+    assert_lr(&scheduler, 60, 1.0);
+}
+
+#[test]
+fn linear_warmup_then_constant() {
+    let scheduler = scheduler(SchedulerConfig::LinearWarmup { warmup_epochs: 2 });
+
+    assert_lr(&scheduler, 0, 0.05);
+    assert_lr(&scheduler, 9, 0.5);
+    assert_lr(&scheduler, 19, 1.0);
+    assert_lr(&scheduler, 50, 1.0);
+}
+
+#[test]
+fn one_cycle_peaks_at_pct_start_and_ends_divided_by_both_factors() {
+    // 101 iterations, so the peak is at iteration 30 and the last one is 100
+    let scheduler = SchedulerConfig::OneCycle {
+        pct_start: 0.3,
+        div_factor: 25.0,
+        final_div_factor: 1e4,
+    }
+    .init(0.1, 1, 101);
+
+    assert_lr(&scheduler, 0, 0.1 / 25.0);
+    assert_lr(&scheduler, 30, 0.1);
+    assert_lr(&scheduler, 100, 0.1 / (25.0 * 1e4));
+
+    let lrs: Vec<f64> = (0..=100)
+        .map(|iteration| scheduler.learning_rate_at(iteration))
+        .collect();
+    assert!(lrs[..=30].windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(lrs[30..].windows(2).all(|pair| pair[0] > pair[1]));
+}
+
+#[test]
+fn stepping_resumes_from_the_record() {
+    let config = SchedulerConfig::StepDecay {
+        step_size: 1,
+        gamma: 0.5,
+    };
+    let mut stepped = scheduler(config);
+
+    let lrs: Vec<f64> = (0..15)
+        .map(|_| LrScheduler::<NdArray>::step(&mut stepped))
+        .collect();
+    assert_eq!(lrs[9], 1.0);
+    assert_eq!(lrs[10], 0.5);
+
+    let record = LrScheduler::<NdArray>::to_record(&stepped);
+    let mut resumed = LrScheduler::<NdArray>::load_record(scheduler(config), record);
+    assert_eq!(
+        LrScheduler::<NdArray>::step(&mut resumed),
+        LrScheduler::<NdArray>::step(&mut stepped)
+    );
+    assert_eq!(LrScheduler::<NdArray>::step(&mut resumed), 0.5);
+}
+
+#[test]
+fn invalid_parameters_are_rejected() {
+    let one_cycle = |pct_start, div_factor, final_div_factor| SchedulerConfig::OneCycle {
+        pct_start,
+        div_factor,
+        final_div_factor,
+    };
+    let invalid = [
+        one_cycle(0.0, 25.0, 1e4),
+        one_cycle(1.0, 25.0, 1e4),
+        one_cycle(f64::NAN, 25.0, 1e4),
+        one_cycle(0.3, 0.0, 1e4),
+        one_cycle(0.3, 25.0, -1.0),
+        SchedulerConfig::StepDecay {
+            step_size: 0,
+            gamma: 0.5,
+        },
+        SchedulerConfig::StepDecay {
+            step_size: 2,
+            gamma: 0.0,
+        },
+        SchedulerConfig::Exponential { gamma: -0.5 },
+        SchedulerConfig::CosineWarmRestarts {
+            period: 0,
+            period_mult: 2,
+            min_lr: 0.0,
+        },
+        SchedulerConfig::CosineWarmRestarts {
+            period: 2,
+            period_mult: 1,
+            min_lr: -0.1,
+        },
+    ];
+
+    for config in invalid {
+        assert!(
+            std::panic::catch_unwind(|| scheduler(config)).is_err(),
+            "{config} was accepted"
+        );
+    }
+}
+
+#[test]
+fn one_cycle_learning_rates_are_finite() {
+    let scheduler = scheduler(SchedulerConfig::OneCycle {
+        pct_start: 0.99,
+        div_factor: 25.0,
+        final_div_factor: 1e4,
+    });
+
+    assert!((0..100).all(|iteration| scheduler.learning_rate_at(iteration).is_finite()));
+}
@@ -0,0 +1,41 @@
+use inside_deep_learning_with_burn::spirals_data::data::{SpiralsConfig, SpiralsItem};
+
+fn keys(items: &[SpiralsItem]) -> Vec<(u32, u32, i8)> {
+    items
+        .iter()
+        .map(|item| (item.x[0].to_bits(), item.x[1].to_bits(), item.y))
+        .collect()
+}
+
+#[test]
+fn every_arm_has_the_same_number_of_samples() {
+    let items = SpiralsConfig::new(25, 3).generate();
+
+    assert_eq!(items.len(), 75);
+    for arm in 0..3 {
+        assert_eq!(items.iter().filter(|item| item.y == arm).count(), 25);
+    }
+}
+
+#[test]
+fn arms_grow_from_the_origin_to_the_unit_circle() {
+    let items = SpiralsConfig::new(11, 2).with_shuffle(false).generate();
+
+    for arm in items.chunks(11) {
+        let radii: Vec<f32> = arm.iter().map(|item| item.x[0].hypot(item.x[1])).collect();
+        assert!(radii[0].abs() < 1e-6);
+        assert!((radii[10] - 1.0).abs() < 1e-6);
+        assert!(radii.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+}
+
+#[test]
+fn is_deterministic_for_a_seed() {
+    let config = SpiralsConfig::new(30, 2).with_noise(Some(0.05));
+
+    assert_eq!(keys(&config.generate()), keys(&config.generate()));
+    assert_ne!(
+        keys(&config.generate()),
+        keys(&config.clone().with_seed(22).generate())
+    );
+}
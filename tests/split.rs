@@ -0,0 +1,118 @@
+use std::collections::HashSet;
+
+use burn::data::dataset::Dataset;
+use inside_deep_learning_with_burn::moons_data::data::{
+    MoonDatasetConfig, MoonsConfig, MoonsDataset,
+};
+use inside_deep_learning_with_burn::split::SplitConfig;
+use inside_deep_learning_with_burn::toy_data::data::{ToyDataset, ToyDatasetConfig};
+
+fn split_config(stratify: bool) -> SplitConfig {
+    SplitConfig {
+        train: 0.7,
+        valid: 0.2,
+        seed: 12,
+        stratify,
+    }
+}
+
+fn moons_keys(dataset: &MoonsDataset) -> HashSet<(u32, u32)> {
+    dataset
+        .iter()
+        .map(|item| (item.x[0].to_bits(), item.x[1].to_bits()))
+        .collect()
+}
+
+fn toy_keys(dataset: &ToyDataset) -> HashSet<u32> {
+    dataset.iter().map(|item| item.x.to_bits()).collect()
+}
+
+#[test]
+fn split_partitions_are_disjoint_and_complete() {
+    let splits = split_config(false).split((0..1000).collect::<Vec<usize>>());
+
+    assert_eq!(splits.train.len(), 700);
+    assert_eq!(splits.valid.len(), 200);
+    assert_eq!(splits.test.len(), 100);
+
+    let train: HashSet<_> = splits.train.iter().collect();
+    let valid: HashSet<_> = splits.valid.iter().collect();
+    let test: HashSet<_> = splits.test.iter().collect();
+
+    assert!(train.is_disjoint(&valid));
+    assert!(train.is_disjoint(&test));
+    assert!(valid.is_disjoint(&test));
+    assert_eq!(train.len() + valid.len() + test.len(), 1000);
+}
+
+#[test]
+fn split_is_deterministic_for_a_seed() {
+    let items: Vec<usize> = (0..100).collect();
+
+    let first = split_config(false).split(items.clone());
+    let second = split_config(false).split(items.clone());
+    let other = SplitConfig {
+        seed: 13,
+        ..split_config(false)
+    }
+    .split(items);
+
+    assert_eq!(first.train, second.train);
+    assert_eq!(first.valid, second.valid);
+    assert_eq!(first.test, second.test);
+    assert_ne!(first.train, other.train);
+}
+
+#[test]
+fn stratified_split_keeps_class_proportions() {
+    // 800 items of class 0 and 200 of class 1
+    let items: Vec<(usize, i8)> = (0..1000).map(|i| (i, (i % 5 == 0) as i8)).collect();
+    let splits = split_config(true).split_by(items, |item| item.1);
+
+    let count = |items: &[(usize, i8)]| items.iter().filter(|item| item.1 == 1).count();
+
+    assert_eq!(count(&splits.train), 140);
+    assert_eq!(count(&splits.valid), 40);
+    assert_eq!(count(&splits.test), 20);
+}
+
+#[test]
+fn moons_splits_do_not_overlap() {
+    let config = MoonDatasetConfig {
+        moons: MoonsConfig::new(200, 300).with_noise(Some(0.1)),
+        split: split_config(true),
+    };
+
+    let train = moons_keys(&config.train());
+    let valid = moons_keys(&config.valid());
+    let test = moons_keys(&config.test());
+
+    assert_eq!(train.len(), 350);
+    assert_eq!(valid.len(), 100);
+    assert_eq!(test.len(), 50);
+    assert!(train.is_disjoint(&valid));
+    assert!(train.is_disjoint(&test));
+    assert!(valid.is_disjoint(&test));
+
+    let inner = config.train().iter().filter(|item| item.y == 1).count();
+    assert_eq!(inner, 210);
+}
+
+#[test]
+fn toy_splits_do_not_overlap() {
+    let config = ToyDatasetConfig {
+        start: 0.0,
+        end: 20.0,
+        n: 500,
+        split: split_config(false),
+    };
+
+    let train = toy_keys(&config.train());
+    let valid = toy_keys(&config.valid());
+    let test = toy_keys(&config.test());
+
+    assert_eq!(train.len() + valid.len() + test.len(), 500);
+    assert!(train.is_disjoint(&valid));
+    assert!(train.is_disjoint(&test));
+    assert!(valid.is_disjoint(&test));
+}
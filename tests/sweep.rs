@@ -0,0 +1,258 @@
+use inside_deep_learning_with_burn::early_stopping::Mode;
+use inside_deep_learning_with_burn::sweep::{
+    Search, SweepConfig, SweepError, SweepParameter, SweepReport, SweepRun, SweepValues,
+};
+use serde_json::{json, Value};
+
+fn parameter(key: &str, values: SweepValues) -> SweepParameter {
+    SweepParameter::new(key.to_string(), values)
+}
+
+fn list(values: &[Value]) -> SweepValues {
+    SweepValues::List(values.to_vec())
+}
+
+fn random(parameters: Vec<SweepParameter>, seed: u64) -> SweepConfig {
+    SweepConfig::new(parameters)
+        .with_search(Search::Random { num_runs: 20 })
+        .with_seed(seed)
+}
+
+fn random_parameters() -> Vec<SweepParameter> {
+    vec![
+        parameter(
+            "training.learning_rate",
+            SweepValues::LogUniform {
+                min: 1e-4,
+                max: 1e-1,
+            },
+        ),
+        parameter(
+            "training.momentum",
+            SweepValues::Uniform { min: 0.5, max: 0.9 },
+        ),
+        parameter(
+            "training.batch_size",
+            SweepValues::Integer { min: 16, max: 64 },
+        ),
+        parameter("model.hidden", list(&[json!([30]), json!([64, 64])])),
+    ]
+}
+
+#[test]
+fn grid_expands_every_combination_in_order() {
+    let sweep = SweepConfig::new(vec![
+        parameter("model.hidden", list(&[json!([30]), json!([64, 64])])),
+        parameter(
+            "training.learning_rate",
+            list(&[json!(0.1), json!(0.01), json!(0.001)]),
+        ),
+    ]);
+
+    let trials = sweep.trials().unwrap();
+
+    assert_eq!(trials.len(), 6);
+    let values: Vec<(Value, Value)> = trials
+        .iter()
+        .map(|trial| {
+            assert_eq!(trial[0].0, "model.hidden");
+            assert_eq!(trial[1].0, "training.learning_rate");
+            (trial[0].1.clone(), trial[1].1.clone())
+        })
+        .collect();
+    assert_eq!(
+        values,
+        [
+            (json!([30]), json!(0.1)),
+            (json!([30]), json!(0.01)),
+            (json!([30]), json!(0.001)),
+            (json!([64, 64]), json!(0.1)),
+            (json!([64, 64]), json!(0.01)),
+            (json!([64, 64]), json!(0.001)),
+        ]
+    );
+}
+
+#[test]
+fn grid_only_takes_lists() {
+    let sweep = SweepConfig::new(vec![parameter(
+        "training.learning_rate",
+        SweepValues::Uniform { min: 0.1, max: 1.0 },
+    )]);
+
+    assert!(matches!(
+        sweep.trials(),
+        Err(SweepError::NotAList { key }) if key == "training.learning_rate"
+    ));
+}
+
+#[test]
+fn random_trials_depend_only_on_the_seed() {
+    let trials = random(random_parameters(), 7).trials().unwrap();
+
+    assert_eq!(trials.len(), 20);
+    assert_eq!(trials, random(random_parameters(), 7).trials().unwrap());
+    assert_ne!(trials, random(random_parameters(), 8).trials().unwrap());
+}
+
+#[test]
+fn random_values_stay_in_their_ranges() {
+    let trials = random(random_parameters(), 3).trials().unwrap();
+
+    for trial in &trials {
+        let learning_rate = trial[0].1.as_f64().unwrap();
+        let momentum = trial[1].1.as_f64().unwrap();
+        let batch_size = trial[2].1.as_i64().unwrap();
+
+        assert!((1e-4..=1e-1).contains(&learning_rate), "{learning_rate}");
+        assert!((0.5..=0.9).contains(&momentum), "{momentum}");
+        assert!((16..=64).contains(&batch_size), "{batch_size}");
+        assert!([json!([30]), json!([64, 64])].contains(&trial[3].1));
+    }
+
+    // Log-uniform draws should spread over the decades rather than pile up near max
+    assert!(trials
+        .iter()
+        .any(|trial| trial[0].1.as_f64().unwrap() < 1e-2));
+}
+
+#[test]
+fn invalid_ranges_are_rejected() {
+    let invalid = [
+        SweepValues::Uniform { min: 1.0, max: 0.1 },
+        SweepValues::Uniform {
+            min: f64::NAN,
+            max: 1.0,
+        },
+        SweepValues::LogUniform { min: 1.0, max: 0.1 },
+        SweepValues::LogUniform { min: 0.0, max: 0.1 },
+        SweepValues::LogUniform {
+            min: -1.0,
+            max: 0.1,
+        },
+        SweepValues::Integer { min: 5, max: 4 },
+    ];
+
+    for values in invalid {
+        let message = format!("{values:?}");
+        let sweep = random(vec![parameter("training.learning_rate", values)], 0);
+
+        assert!(
+            matches!(
+                sweep.trials(),
+                Err(SweepError::InvalidRange { key, .. }) if key == "training.learning_rate"
+            ),
+            "{message}"
+        );
+    }
+}
+
+#[test]
+fn empty_lists_are_rejected() {
+    let sweep = SweepConfig::new(vec![parameter("model.hidden", list(&[]))]);
+
+    assert!(matches!(
+        sweep.validate(),
+        Err(SweepError::EmptyValues { key }) if key == "model.hidden"
+    ));
+}
+
+#[test]
+fn invalid_ranges_are_rejected_when_loading() {
+    let path = std::env::temp_dir().join(format!("sweep-{}.json", std::process::id()));
+    std::fs::write(
+        &path,
+        r#"{
+            "parameters": [
+                { "key": "training.learning_rate", "values": { "LogUniform": { "min": 0, "max": 0.1 } } }
+            ],
+            "search": { "Random": { "num_runs": 4 } },
+            "metric": "valid/Loss",
+            "mode": "Min",
+            "seed": 42
+        }"#,
+    )
+    .unwrap();
+
+    let result = SweepConfig::load_file(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(matches!(result, Err(SweepError::InvalidRange { .. })));
+}
+
+#[test]
+fn report_ranks_the_runs_by_metric() {
+    let run = |id: &str, metric| SweepRun {
+        id: id.to_string(),
+        trial: vec![("training.learning_rate".to_string(), json!(0.1))],
+        metric,
+    };
+    let runs = vec![
+        run("a", Some(0.5)),
+        run("b", None),
+        run("c", Some(0.2)),
+        run("d", Some(0.9)),
+    ];
+    let sweep = SweepConfig::new(vec![parameter(
+        "training.learning_rate",
+        list(&[json!(0.1)]),
+    )]);
+
+    let ids = |report: SweepReport| -> Vec<String> {
+        report.runs.into_iter().map(|run| run.id).collect()
+    };
+    assert_eq!(
+        ids(SweepReport::new(&sweep, runs.clone())),
+        ["c", "a", "d", "b"]
+    );
+    assert_eq!(
+        ids(SweepReport::new(&sweep.with_mode(Mode::Max), runs)),
+        ["d", "a", "c", "b"]
+    );
+}
+
+#[test]
+fn random_searches_need_runs() {
+    let sweep = SweepConfig::new(random_parameters()).with_search(Search::Random { num_runs: 0 });
+
+    assert!(matches!(sweep.validate(), Err(SweepError::NoRuns)));
+    assert!(matches!(sweep.trials(), Err(SweepError::NoRuns)));
+}
+
+#[test]
+fn random_trials_are_distinct() {
+    let parameters = || {
+        vec![
+            parameter("model.hidden", list(&[json!([30]), json!([64, 64])])),
+            parameter(
+                "training.batch_size",
+                SweepValues::Integer { min: 16, max: 18 },
+            ),
+        ]
+    };
+    let trials = |parameters, num_runs| {
+        SweepConfig::new(parameters)
+            .with_search(Search::Random { num_runs })
+            .trials()
+            .unwrap()
+    };
+
+    let some = trials(parameters(), 4);
+    assert_eq!(some.len(), 4);
+    assert!(some
+        .iter()
+        .enumerate()
+        .all(|(i, trial)| !some[..i].contains(trial)));
+
+    // Only 6 combinations, each drawn once
+    let all = trials(parameters(), 20);
+    assert_eq!(all.len(), 6);
+    assert!(all
+        .iter()
+        .enumerate()
+        .all(|(i, trial)| !all[..i].contains(trial)));
+
+    // A repeated value adds no combination
+    let repeated = vec![parameter("model.hidden", list(&[json!([30]), json!([30])]))];
+    assert_eq!(trials(repeated, 3).len(), 1);
+}
@@ -0,0 +1,323 @@
+use inside_deep_learning_with_burn::transform::{
+    Image, Transform, TransformError, TransformPipeline,
+};
+
+// 2 channels of 2x3 pixels, the first counts from 0 and the second from 10
+fn image() -> Image {
+    Image::new(
+        2,
+        2,
+        3,
+        vec![
+            0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0,
+        ],
+    )
+}
+
+fn apply(transform: Transform, image: Image) -> Image {
+    let shape = transform.output_shape(image.shape()).unwrap();
+    let image = transform.apply(image).unwrap();
+    assert_eq!(image.shape(), shape);
+    image
+}
+
+fn assert_close(found: &[f32], expected: &[f32]) {
+    assert_eq!(found.len(), expected.len());
+    for (found, expected) in found.iter().zip(expected) {
+        assert!(
+            (found - expected).abs() < 1e-5,
+            "expected {expected:?}, found {found:?}"
+        );
+    }
+}
+
+#[test]
+fn scale_multiplies_every_pixel() {
+    let scaled = apply(Transform::Scale { factor: 0.5 }, image());
+
+    assert_eq!(scaled.shape(), [2, 2, 3]);
+    assert_eq!(scaled.channel(0), [0.0, 0.5, 1.0, 1.5, 2.0, 2.5]);
+    assert_eq!(scaled.channel(1), [5.0, 5.5, 6.0, 6.5, 7.0, 7.5]);
+}
+
+#[test]
+fn normalize_uses_the_statistics_of_each_channel() {
+    let normalized = apply(
+        Transform::Normalize {
+            mean: vec![1.0, 10.0],
+            std: vec![2.0, 5.0],
+        },
+        image(),
+    );
+
+    assert_close(normalized.channel(0), &[-0.5, 0.0, 0.5, 1.0, 1.5, 2.0]);
+    assert_close(normalized.channel(1), &[0.0, 0.2, 0.4, 0.6, 0.8, 1.0]);
+}
+
+#[test]
+fn normalize_shares_a_single_statistic() {
+    let normalized = apply(
+        Transform::Normalize {
+            mean: vec![1.0],
+            std: vec![2.0],
+        },
+        image(),
+    );
+
+    assert_close(normalized.channel(0), &[-0.5, 0.0, 0.5, 1.0, 1.5, 2.0]);
+    assert_close(normalized.channel(1), &[4.5, 5.0, 5.5, 6.0, 6.5, 7.0]);
+}
+
+#[test]
+fn normalize_needs_one_statistic_per_channel() {
+    let transform = Transform::Normalize {
+        mean: vec![0.0, 0.0, 0.0],
+        std: vec![1.0],
+    };
+    let error = TransformError::Statistics {
+        channels: 2,
+        mean: 3,
+        std: 1,
+    };
+
+    assert_eq!(transform.output_shape([2, 2, 3]), Err(error));
+    assert!(matches!(
+        transform.apply(image()),
+        Err(TransformError::Statistics { channels: 2, .. })
+    ));
+}
+
+#[test]
+fn pad_surrounds_the_image() {
+    let padded = apply(
+        Transform::Pad {
+            top: 1,
+            bottom: 0,
+            left: 2,
+            right: 1,
+            value: -1.0,
+        },
+        image(),
+    );
+
+    assert_eq!(padded.shape(), [2, 3, 6]);
+    assert_eq!(
+        padded.channel(0),
+        [
+            -1.0, -1.0, -1.0, -1.0, -1.0, -1.0, //
+            -1.0, -1.0, 0.0, 1.0, 2.0, -1.0, //
+            -1.0, -1.0, 3.0, 4.0, 5.0, -1.0,
+        ]
+    );
+    assert_eq!(padded.get(1, 2, 4), 15.0);
+}
+
+#[test]
+fn crop_keeps_the_window() {
+    let cropped = apply(
+        Transform::Crop {
+            top: 1,
+            left: 1,
+            height: 1,
+            width: 2,
+        },
+        image(),
+    );
+
+    assert_eq!(cropped.shape(), [2, 1, 2]);
+    assert_eq!(cropped.channel(0), [4.0, 5.0]);
+    assert_eq!(cropped.channel(1), [14.0, 15.0]);
+}
+
+#[test]
+fn crop_outside_of_the_image_is_rejected() {
+    let transform = Transform::Crop {
+        top: 1,
+        left: 2,
+        height: 1,
+        width: 2,
+    };
+
+    assert_eq!(
+        transform.apply(image()),
+        Err(TransformError::Crop {
+            top: 1,
+            left: 2,
+            height: 1,
+            width: 2,
+            image: [2, 3],
+        })
+    );
+}
+
+#[test]
+fn center_crop_keeps_the_middle() {
+    let image = Image::new(1, 4, 4, (0..16).map(|i| i as f32).collect());
+
+    let cropped = apply(
+        Transform::CenterCrop {
+            height: 2,
+            width: 2,
+        },
+        image,
+    );
+
+    assert_eq!(cropped.data, [5.0, 6.0, 9.0, 10.0]);
+    assert!(matches!(
+        Transform::CenterCrop {
+            height: 3,
+            width: 3
+        }
+        .output_shape([2, 2, 3]),
+        Err(TransformError::Crop { .. })
+    ));
+}
+
+#[test]
+fn resize_interpolates_between_pixel_centers() {
+    let image = Image::new(1, 2, 2, vec![0.0, 4.0, 8.0, 12.0]);
+
+    let resized = apply(
+        Transform::Resize {
+            height: 4,
+            width: 4,
+        },
+        image.clone(),
+    );
+
+    assert_close(
+        &resized.data,
+        &[
+            0.0, 1.0, 3.0, 4.0, //
+            2.0, 3.0, 5.0, 6.0, //
+            6.0, 7.0, 9.0, 10.0, //
+            8.0, 9.0, 11.0, 12.0,
+        ],
+    );
+    let same = Transform::Resize {
+        height: 2,
+        width: 2,
+    };
+    assert_eq!(apply(same, image.clone()), image);
+}
+
+#[test]
+fn resize_needs_pixels() {
+    let empty = Image::new(1, 0, 3, Vec::new());
+    let transform = Transform::Resize {
+        height: 2,
+        width: 2,
+    };
+
+    assert_eq!(
+        transform.apply(empty),
+        Err(TransformError::Resize {
+            from: [0, 3],
+            to: [2, 2],
+        })
+    );
+    assert!(Transform::Resize {
+        height: 0,
+        width: 2
+    }
+    .output_shape([1, 2, 2])
+    .is_err());
+}
+
+#[test]
+fn grayscale_averages_the_channels() {
+    let gray = apply(Transform::Grayscale, image());
+
+    assert_eq!(gray.shape(), [1, 2, 3]);
+    assert_eq!(gray.data, [5.0, 6.0, 7.0, 8.0, 9.0, 10.0]);
+}
+
+#[test]
+fn repeat_channels_cycles_through_them() {
+    let repeated = apply(Transform::RepeatChannels { channels: 4 }, image());
+
+    assert_eq!(repeated.shape(), [4, 2, 3]);
+    assert_eq!(repeated.channel(2), image().channel(0));
+    assert_eq!(repeated.channel(3), image().channel(1));
+    assert_eq!(
+        Transform::RepeatChannels { channels: 3 }.apply(image()),
+        Err(TransformError::RepeatChannels { from: 2, to: 3 })
+    );
+}
+
+#[test]
+fn pipeline_applies_the_transforms_in_order() {
+    let pad = Transform::Pad {
+        top: 0,
+        bottom: 0,
+        left: 1,
+        right: 0,
+        value: 1.0,
+    };
+    let scale = Transform::Scale { factor: 2.0 };
+    let image = Image::new(1, 1, 1, vec![3.0]);
+
+    let pad_then_scale = TransformPipeline::identity()
+        .then(pad.clone())
+        .then(scale.clone());
+    let scale_then_pad = TransformPipeline::identity().then(scale).then(pad);
+
+    assert_eq!(
+        pad_then_scale.apply(image.clone()).unwrap().data,
+        [2.0, 6.0]
+    );
+    assert_eq!(scale_then_pad.apply(image).unwrap().data, [1.0, 6.0]);
+}
+
+#[test]
+fn pipeline_checks_each_transform_against_the_shape_it_gets() {
+    let pipeline = TransformPipeline::identity()
+        .then(Transform::Grayscale)
+        .then(Transform::Normalize {
+            mean: vec![0.5, 0.5],
+            std: vec![0.5, 0.5],
+        });
+
+    // Normalizing 2 channels is fine, but the grayscale image only has 1
+    assert_eq!(
+        pipeline.output_shape([2, 2, 3]),
+        Err(TransformError::Statistics {
+            channels: 1,
+            mean: 2,
+            std: 2,
+        })
+    );
+    assert!(pipeline.apply(image()).is_err());
+    assert_eq!(
+        TransformPipeline::mnist().output_shape([1, 28, 28]),
+        Ok([1, 28, 28])
+    );
+}
+
+#[test]
+fn statistics_of_known_images() {
+    let images = [
+        Image::new(2, 1, 2, vec![1.0, 2.0, 0.1, 0.1]),
+        Image::new(2, 1, 2, vec![3.0, 4.0, 0.1, 0.1]),
+    ];
+
+    let statistics = TransformPipeline::identity().statistics(images).unwrap();
+
+    assert_close(&statistics.mean, &[2.5, 0.1]);
+    assert_close(&statistics.std, &[1.25f32.sqrt(), 0.0]);
+    // A constant channel has no variance, rather than a rounding error below 0
+    assert_eq!(statistics.std[1], 0.0);
+}
+
+#[test]
+fn statistics_are_computed_after_the_transforms() {
+    let images = [Image::new(1, 1, 2, vec![0.0, 255.0])];
+    let pipeline = TransformPipeline::new(vec![Transform::Scale {
+        factor: 1.0 / 255.0,
+    }]);
+
+    let statistics = pipeline.statistics(images).unwrap();
+
+    assert_close(&statistics.mean, &[0.5]);
+    assert_close(&statistics.std, &[0.5]);
+}
@@ -0,0 +1,106 @@
+use std::cell::RefCell;
+
+use burn::backend::NdArray;
+use burn::tensor::Tensor;
+use inside_deep_learning_with_burn::visualization::DecisionBoundaryConfig;
+
+const POINTS: [[f32; 2]; 3] = [[0.0, 1.0], [2.0, -1.0], [1.0, 3.0]];
+
+fn close(found: &[f32], expected: &[f32]) -> bool {
+    found.len() == expected.len()
+        && found
+            .iter()
+            .zip(expected)
+            .all(|(found, expected)| (found - expected).abs() < 1e-6)
+}
+
+#[test]
+fn grid_covers_the_padded_points() {
+    let config = DecisionBoundaryConfig::new()
+        .with_resolution(3)
+        .with_padding(0.5);
+
+    let (x1, x2) = config.grid(&POINTS);
+
+    assert!(close(&x1, &[-0.5, 1.0, 2.5]), "{x1:?}");
+    assert!(close(&x2, &[-1.5, 1.0, 3.5]), "{x2:?}");
+    assert!(close(&config.grid(&[]).0, &[-1.0, 0.0, 1.0]));
+}
+
+#[test]
+#[should_panic(expected = "at least 2 points per axis")]
+fn grids_need_two_points_per_axis() {
+    DecisionBoundaryConfig::new()
+        .with_resolution(1)
+        .grid(&POINTS);
+}
+
+#[test]
+#[should_panic(expected = "at least 2 points per axis")]
+fn plots_need_two_points_per_axis() {
+    DecisionBoundaryConfig::new().with_resolution(1).plot(
+        |x: Tensor<NdArray, 2>| x,
+        &POINTS,
+        &[0, 1, 0],
+        &Default::default(),
+    );
+}
+
+#[test]
+fn classifier_is_evaluated_on_every_grid_point() {
+    let seen = RefCell::new(Vec::new());
+    let config = DecisionBoundaryConfig::new()
+        .with_resolution(3)
+        .with_padding(0.5);
+
+    config.plot(
+        |x: Tensor<NdArray, 2>| {
+            seen.borrow_mut()
+                .extend(x.clone().into_data().convert::<f32>().value);
+            x
+        },
+        &POINTS,
+        &[0, 1, 0],
+        &Default::default(),
+    );
+
+    // Rows of the second feature, each going along the first one
+    let seen = seen.into_inner();
+    assert_eq!(seen.len(), 3 * 3 * 2);
+    assert!(close(&seen[..6], &[-0.5, -1.5, 1.0, -1.5, 2.5, -1.5]));
+    assert!(close(&seen[12..], &[-0.5, 3.5, 1.0, 3.5, 2.5, 3.5]));
+}
+
+#[test]
+fn binary_classifiers_plot_a_contour() {
+    let plot = DecisionBoundaryConfig::new().with_resolution(4).plot(
+        |x: Tensor<NdArray, 2>| x,
+        &POINTS,
+        &[0, 1, 0],
+        &Default::default(),
+    );
+
+    // The probability of class 1 and the points of each class
+    assert_eq!(plot.data().len(), 3);
+    let json = plot.to_json();
+    assert_eq!(json.matches("\"type\":\"contour\"").count(), 1);
+    assert!(!json.contains("\"type\":\"heatmap\""));
+    assert!(json.contains("P(class 1)"));
+}
+
+#[test]
+fn multiclass_classifiers_plot_a_heatmap() {
+    let plot = DecisionBoundaryConfig::new().with_resolution(4).plot(
+        |x: Tensor<NdArray, 2>| Tensor::cat(vec![x.clone(), x.sum_dim(1)], 1),
+        &POINTS,
+        &[0, 1, 2],
+        &Default::default(),
+    );
+
+    // The predicted class, the hidden probability of each class and the points
+    assert_eq!(plot.data().len(), 1 + 3 + 3);
+    let json = plot.to_json();
+    assert_eq!(json.matches("\"type\":\"heatmap\"").count(), 1);
+    assert_eq!(json.matches("\"type\":\"contour\"").count(), 3);
+    assert_eq!(json.matches("\"visible\":\"legendonly\"").count(), 3);
+}
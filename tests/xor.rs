@@ -0,0 +1,41 @@
+use inside_deep_learning_with_burn::xor_data::data::{XorConfig, XorItem};
+
+fn keys(items: &[XorItem]) -> Vec<(u32, u32, i8)> {
+    items
+        .iter()
+        .map(|item| (item.x[0].to_bits(), item.x[1].to_bits(), item.y))
+        .collect()
+}
+
+#[test]
+fn labels_are_the_xor_of_the_signs() {
+    let items = XorConfig::new(500).generate();
+
+    assert_eq!(items.len(), 500);
+    for item in items {
+        assert!(item.x.iter().all(|value| (-1.0..1.0).contains(value)));
+        let expected = (item.x[0] >= 0.0) != (item.x[1] >= 0.0);
+        assert_eq!(item.y, expected as i8, "{item:?}");
+    }
+}
+
+#[test]
+fn classes_are_balanced() {
+    for cells in [2, 4] {
+        let items = XorConfig::new(4000).with_cells(cells).generate();
+        let ones = items.iter().filter(|item| item.y == 1).count();
+
+        assert!((1800..2200).contains(&ones), "{ones} of 4000 items are 1");
+    }
+}
+
+#[test]
+fn is_deterministic_for_a_seed() {
+    let config = XorConfig::new(100).with_noise(Some(0.05));
+
+    assert_eq!(keys(&config.generate()), keys(&config.generate()));
+    assert_ne!(
+        keys(&config.generate()),
+        keys(&config.clone().with_seed(22).generate())
+    );
+}